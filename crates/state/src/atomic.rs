@@ -0,0 +1,101 @@
+use core::sync::atomic::{AtomicU16, Ordering};
+
+use crate::State;
+
+/// A state shared between one producer (e.g. the BLE input path) and the shift-out ISR without a critical section.
+///
+/// The published word lives in a single `AtomicU16`, which is what keeps a reader from ever seeing half of an old
+/// and half of a new word. The second buffer is the ISR's own copy: `latch` hands out a `State` that the ISR clocks
+/// out by itself, so a `publish` in the middle of a shift only shows up on the next latch. Only atomic loads and
+/// stores are used, which the ESP32-C3 supports without the A extension.
+pub struct AtomicState {
+    published: AtomicU16,
+}
+
+impl AtomicState {
+    pub const fn new(state: State) -> Self {
+        Self {
+            published: AtomicU16::new(state.value),
+        }
+    }
+
+    /// Makes `state` the one the next `snapshot` or `latch` returns.
+    pub fn publish(&self, state: State) {
+        self.published.store(state.value, Ordering::Release);
+    }
+
+    pub fn snapshot(&self) -> State {
+        State::from_u16(self.published.load(Ordering::Acquire))
+    }
+
+    /// Takes a copy of the latest published state with its cycle reset, ready to be clocked out after a latch pulse.
+    pub fn latch(&self) -> State {
+        let mut state = self.snapshot();
+        state.reset_cycle();
+        state
+    }
+}
+
+impl Default for AtomicState {
+    fn default() -> Self {
+        Self::new(State::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::thread;
+
+    use super::*;
+    use crate::{BUTTONS_MASK, BUTTON_A, BUTTON_B, BUTTON_Y};
+
+    #[test]
+    fn latch_resets_the_cycle() {
+        let mut state = State::with_buttons_pressed(BUTTON_A);
+        state.next();
+        let shared = AtomicState::new(state);
+
+        assert_eq!(shared.snapshot().cycle(), 1);
+        assert_eq!(shared.latch(), State::with_buttons_pressed(BUTTON_A));
+    }
+
+    #[test]
+    fn publish_during_a_shift_waits_for_the_next_latch() {
+        let shared = AtomicState::new(State::with_buttons_pressed(BUTTON_B));
+        let mut shifting = shared.latch();
+        assert!(!shifting.next(), "B of the latched frame");
+
+        shared.publish(State::with_buttons_pressed(BUTTON_Y));
+        assert!(
+            shifting.next(),
+            "Y is released in the frame being clocked out"
+        );
+        assert_eq!(shared.snapshot().cycle(), 0);
+
+        let mut shifting = shared.latch();
+        assert!(shifting.next(), "B is released in the new frame");
+        assert!(!shifting.next(), "Y of the new frame");
+    }
+
+    #[test]
+    fn concurrent_snapshots_are_never_torn() {
+        let first = State::with_buttons_pressed(BUTTON_A | BUTTON_B);
+        let second = State::with_buttons_pressed(!(BUTTON_A | BUTTON_B) & BUTTONS_MASK);
+        let shared = AtomicState::new(first);
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                for n in 0..100_000 {
+                    shared.publish(if n % 2 == 0 { second } else { first });
+                }
+            });
+
+            for _ in 0..100_000 {
+                let snapshot = shared.snapshot();
+                assert!(snapshot == first || snapshot == second, "{snapshot}");
+            }
+        });
+    }
+}
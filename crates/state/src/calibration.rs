@@ -0,0 +1,200 @@
+use crate::{dpad::Direction, State};
+
+/// Full deflection of an axis in the scale `ENGAGE` and `RELEASE` are given in.
+const FULL_SCALE: i16 = 1000;
+
+/// Deflection a direction engages at, and the one it releases at.
+const ENGAGE: i16 = FULL_SCALE / 2;
+const RELEASE: i16 = FULL_SCALE / 4;
+
+/// Measured range of one raw analog axis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AxisRange {
+    pub min: i16,
+    pub center: i16,
+    pub max: i16,
+}
+
+impl AxisRange {
+    /// Deflection reachable in both directions, so both get the same threshold.
+    fn deflection(&self) -> i32 {
+        (self.max as i32 - self.center as i32).min(self.center as i32 - self.min as i32)
+    }
+
+    /// Raw readings at which the D-pad directions of this axis engage and release.
+    pub fn thresholds(&self) -> AxisThresholds {
+        let at = |scaled: i16| (scaled as i32 * self.deflection() / FULL_SCALE as i32) as i16;
+
+        AxisThresholds {
+            engage_positive: self.center + at(ENGAGE),
+            release_positive: self.center + at(RELEASE),
+            engage_negative: self.center - at(ENGAGE),
+            release_negative: self.center - at(RELEASE),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AxisThresholds {
+    pub engage_positive: i16,
+    pub release_positive: i16,
+    pub engage_negative: i16,
+    pub release_negative: i16,
+}
+
+/// Learns the range of an analog stick so devices with different centers and travel map to the D-pad alike.
+///
+/// Call `begin_calibration` with the stick at rest, `sample` while it is moved to its limits, then `finish`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Calibration {
+    x: AxisRange,
+    y: AxisRange,
+    calibrating: bool,
+}
+
+impl Calibration {
+    pub fn begin_calibration(&mut self, x: i16, y: i16) {
+        let rest = |value| AxisRange {
+            min: value,
+            center: value,
+            max: value,
+        };
+
+        self.x = rest(x);
+        self.y = rest(y);
+        self.calibrating = true;
+    }
+
+    pub fn is_calibrating(&self) -> bool {
+        self.calibrating
+    }
+
+    /// Widens the recorded ranges. Ignored when no calibration is running.
+    pub fn sample(&mut self, x: i16, y: i16) {
+        if !self.calibrating {
+            return;
+        }
+
+        for (range, value) in [(&mut self.x, x), (&mut self.y, y)] {
+            range.min = range.min.min(value);
+            range.max = range.max.max(value);
+        }
+    }
+
+    /// Ends the calibration, or returns `None` if an axis was not moved both ways from its center.
+    pub fn finish(&mut self) -> Option<CalibratedDpad> {
+        self.calibrating = false;
+
+        if self.x.deflection() <= 0 || self.y.deflection() <= 0 {
+            return None;
+        }
+
+        Some(CalibratedDpad {
+            x: self.x.thresholds(),
+            y: self.y.thresholds(),
+            x_direction: Direction::Neutral,
+            y_direction: Direction::Neutral,
+        })
+    }
+}
+
+/// D-pad mapping derived from a finished `Calibration`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CalibratedDpad {
+    pub x: AxisThresholds,
+    pub y: AxisThresholds,
+    x_direction: Direction,
+    y_direction: Direction,
+}
+
+impl CalibratedDpad {
+    /// Feeds raw axis readings and writes the resulting directions to the D-pad buttons of `state`.
+    pub fn update(&mut self, x: i16, y: i16, state: &mut State) {
+        self.x_direction = self.x_direction.update(x, &self.x);
+        self.y_direction = self.y_direction.update(y, &self.y);
+
+        Direction::press_dpad(self.x_direction, self.y_direction, state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calibrated() -> CalibratedDpad {
+        let mut calibration = Calibration::default();
+        calibration.begin_calibration(2048, 512);
+        assert!(calibration.is_calibrating());
+
+        for (x, y) in [(3000, 700), (4000, 1023), (1000, 0), (48, 300), (2048, 512)] {
+            calibration.sample(x, y);
+        }
+
+        let dpad = calibration.finish().unwrap();
+        assert!(!calibration.is_calibrating());
+        dpad
+    }
+
+    #[test]
+    fn thresholds_follow_the_recorded_range() {
+        let dpad = calibrated();
+
+        assert_eq!(
+            dpad.x,
+            AxisThresholds {
+                engage_positive: 3024,
+                release_positive: 2536,
+                engage_negative: 1072,
+                release_negative: 1560,
+            }
+        );
+        assert_eq!(
+            dpad.y,
+            AxisThresholds {
+                engage_positive: 767,
+                release_positive: 639,
+                engage_negative: 257,
+                release_negative: 385,
+            }
+        );
+    }
+
+    #[test]
+    fn dpad_engages_and_releases_at_the_thresholds() {
+        let mut dpad = calibrated();
+        let mut state = State::default();
+
+        for (x, right) in [(3000, false), (3024, true), (2536, true), (2535, false)] {
+            dpad.update(x, 512, &mut state);
+            assert_eq!(state.is_right_pressed(), right, "x = {x}");
+        }
+
+        dpad.update(1072, 257, &mut state);
+        assert!(state.is_left_pressed() && state.is_up_pressed());
+
+        dpad.update(1560, 385, &mut state);
+        assert!(state.is_left_pressed() && state.is_up_pressed());
+
+        dpad.update(2048, 1023, &mut state);
+        assert!(state.is_down_pressed());
+        assert!(!state.is_left_pressed() && !state.is_up_pressed() && !state.is_right_pressed());
+    }
+
+    #[test]
+    fn axes_not_moved_both_ways_fail() {
+        let mut calibration = Calibration::default();
+        calibration.begin_calibration(2048, 512);
+        calibration.sample(4000, 0);
+        calibration.sample(4000, 1023);
+
+        assert_eq!(calibration.finish(), None);
+    }
+
+    #[test]
+    fn samples_outside_a_calibration_are_ignored() {
+        let mut calibration = Calibration::default();
+        calibration.sample(4000, 1023);
+
+        assert_eq!(calibration, Calibration::default());
+    }
+}
@@ -0,0 +1,79 @@
+use crate::State;
+
+/// Two controllers carried in one 4-byte payload: player 1's state word followed by player 2's, both little-endian.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CombinedState {
+    pub p1: State,
+    pub p2: State,
+}
+
+impl CombinedState {
+    pub const ENCODED_LEN: usize = 4;
+
+    /// Returns the state of player 1 or 2.
+    pub fn player(&self, player: u8) -> Option<&State> {
+        match player {
+            1 => Some(&self.p1),
+            2 => Some(&self.p2),
+            _ => None,
+        }
+    }
+
+    pub fn player_mut(&mut self, player: u8) -> Option<&mut State> {
+        match player {
+            1 => Some(&mut self.p1),
+            2 => Some(&mut self.p2),
+            _ => None,
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::ENCODED_LEN] {
+        let [p1_lo, p1_hi] = self.p1.value.to_le_bytes();
+        let [p2_lo, p2_hi] = self.p2.value.to_le_bytes();
+
+        [p1_lo, p1_hi, p2_lo, p2_hi]
+    }
+
+    pub fn from_bytes(bytes: [u8; Self::ENCODED_LEN]) -> Self {
+        Self {
+            p1: State::from_u16(u16::from_le_bytes([bytes[0], bytes[1]])),
+            p2: State::from_u16(u16::from_le_bytes([bytes[2], bytes[3]])),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BUTTON_A, BUTTON_START};
+
+    fn pressed(mask: u16) -> State {
+        let mut state = State::default();
+        state.apply_mask(mask);
+        state
+    }
+
+    #[test]
+    fn round_trip_keeps_players_apart() {
+        let mut combined = CombinedState {
+            p1: pressed(BUTTON_A),
+            p2: pressed(BUTTON_START),
+        };
+        combined.player_mut(2).unwrap().next();
+
+        let bytes = combined.to_bytes();
+
+        assert_eq!(bytes[..2], combined.p1.value.to_le_bytes());
+        assert_eq!(CombinedState::from_bytes(bytes), combined);
+    }
+
+    #[test]
+    fn only_players_one_and_two_exist() {
+        let combined = CombinedState::default();
+
+        assert!(combined.player(0).is_none());
+        assert!(combined.player(1).is_some());
+        assert!(combined.player(2).is_some());
+        assert!(combined.player(3).is_none());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{Frame, State};
+
+/// The buttons that toggled since the previously sent delta, tagged with the frame they changed on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StateDelta {
+    pub frame: Frame,
+    /// Toggled buttons in `BUTTON_*` layout.
+    pub toggled: u16,
+}
+
+impl StateDelta {
+    pub const ENCODED_LEN: usize = 6;
+
+    /// Frame number followed by the toggled mask, both little-endian.
+    pub fn to_bytes(&self) -> [u8; Self::ENCODED_LEN] {
+        let [f0, f1, f2, f3] = self.frame.0.to_le_bytes();
+        let [t0, t1] = self.toggled.to_le_bytes();
+
+        [f0, f1, f2, f3, t0, t1]
+    }
+
+    pub fn from_bytes(bytes: [u8; Self::ENCODED_LEN]) -> Self {
+        Self {
+            frame: Frame(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+            toggled: u16::from_le_bytes([bytes[4], bytes[5]]),
+        }
+    }
+}
+
+/// Sender side of the delta stream. Both ends have to start from the same state, by default a neutral pad.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DeltaEncoder {
+    previous: State,
+}
+
+impl DeltaEncoder {
+    pub fn new(initial: State) -> Self {
+        Self { previous: initial }
+    }
+
+    /// Returns the delta to send for `state`, or `None` if no button changed and nothing needs to be sent.
+    pub fn encode(&mut self, frame: Frame, state: State) -> Option<StateDelta> {
+        let diff = self.previous.diff(&state);
+        if diff.is_empty() {
+            return None;
+        }
+
+        self.previous = state;
+
+        Some(StateDelta {
+            frame,
+            toggled: diff.changed(),
+        })
+    }
+}
+
+/// Receiver side of the delta stream.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DeltaDecoder {
+    state: State,
+}
+
+impl DeltaDecoder {
+    pub fn new(initial: State) -> Self {
+        Self { state: initial }
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Applies a received delta and returns the reconstructed state.
+    pub fn apply(&mut self, delta: &StateDelta) -> State {
+        self.state
+            .apply_mask(self.state.pressed_mask() ^ delta.toggled);
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BUTTON_A, BUTTON_B, BUTTON_DOWN};
+
+    fn pressed(mask: u16) -> State {
+        let mut state = State::default();
+        state.apply_mask(mask);
+        state
+    }
+
+    #[test]
+    fn multi_frame_round_trip() {
+        let frames = [
+            pressed(BUTTON_A),
+            pressed(BUTTON_A),
+            pressed(BUTTON_A | BUTTON_DOWN),
+            pressed(BUTTON_B),
+            pressed(BUTTON_B),
+            State::default(),
+        ];
+        let mut encoder = DeltaEncoder::default();
+        let mut decoder = DeltaDecoder::default();
+        let mut sent = 0;
+
+        for (n, &state) in frames.iter().enumerate() {
+            let frame = Frame(n as u32);
+            if let Some(delta) = encoder.encode(frame, state) {
+                let received = StateDelta::from_bytes(delta.to_bytes());
+                assert_eq!(received, delta);
+                assert_eq!(received.frame, frame);
+                decoder.apply(&received);
+                sent += 1;
+            }
+
+            assert_eq!(decoder.state().pressed_mask(), state.pressed_mask());
+        }
+
+        assert_eq!(sent, 4);
+    }
+
+    #[test]
+    fn identical_frames_send_nothing() {
+        let state = pressed(BUTTON_B);
+        let mut encoder = DeltaEncoder::new(state);
+
+        assert_eq!(encoder.encode(Frame(0), state), None);
+        assert_eq!(encoder.encode(Frame(1), state), None);
+    }
+}
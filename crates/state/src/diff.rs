@@ -0,0 +1,101 @@
+use crate::{offset, Button, State};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ButtonEvent {
+    Pressed(Button),
+    Released(Button),
+}
+
+/// Buttons that changed between two states, as masks in `BUTTON_*` layout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StateDiff {
+    pressed: u16,
+    released: u16,
+}
+
+impl StateDiff {
+    /// Buttons that went from released to pressed.
+    pub fn pressed(&self) -> u16 {
+        self.pressed
+    }
+
+    /// Buttons that went from pressed to released.
+    pub fn released(&self) -> u16 {
+        self.released
+    }
+
+    pub fn changed(&self) -> u16 {
+        self.pressed | self.released
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changed() == 0
+    }
+
+    /// Yields one event per changed button in clock-out order.
+    pub fn events(&self) -> impl Iterator<Item = ButtonEvent> {
+        let diff = *self;
+
+        Button::ALL.into_iter().filter_map(move |button| {
+            let mask = 1 << offset(button);
+
+            if diff.pressed & mask != 0 {
+                Some(ButtonEvent::Pressed(button))
+            } else if diff.released & mask != 0 {
+                Some(ButtonEvent::Released(button))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl State {
+    /// Returns the buttons that changed going from `self` to `next`. The cycle is ignored.
+    pub fn diff(&self, next: &State) -> StateDiff {
+        let before = self.pressed_mask();
+        let after = next.pressed_mask();
+
+        StateDiff {
+            pressed: after & !before,
+            released: before & !after,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BUTTON_A, BUTTON_B, BUTTON_L, BUTTON_UP};
+
+    fn pressed(mask: u16) -> State {
+        let mut state = State::default();
+        state.apply_mask(mask);
+        state
+    }
+
+    #[test]
+    fn events_cover_several_buttons_in_clock_out_order() {
+        let before = pressed(BUTTON_B | BUTTON_L);
+        let after = pressed(BUTTON_UP | BUTTON_A | BUTTON_L);
+        let diff = before.diff(&after);
+
+        assert_eq!(diff.pressed(), BUTTON_UP | BUTTON_A);
+        assert_eq!(diff.released(), BUTTON_B);
+
+        let mut events = diff.events();
+        assert_eq!(events.next(), Some(ButtonEvent::Released(Button::B)));
+        assert_eq!(events.next(), Some(ButtonEvent::Pressed(Button::Up)));
+        assert_eq!(events.next(), Some(ButtonEvent::Pressed(Button::A)));
+        assert_eq!(events.next(), None);
+    }
+
+    #[test]
+    fn cycle_changes_alone_are_empty() {
+        let before = pressed(BUTTON_A);
+        let mut after = before;
+        after.next();
+
+        assert!(before.diff(&after).is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{AxisThresholds, Button, State};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Negative,
+    #[default]
+    Neutral,
+    Positive,
+}
+
+impl Direction {
+    /// A direction engages once the axis goes past its engage threshold and only releases once it falls back
+    /// inside its release threshold.
+    pub(crate) fn update(self, value: i16, thresholds: &AxisThresholds) -> Direction {
+        let held = match self {
+            Direction::Negative => value <= thresholds.release_negative,
+            Direction::Neutral => false,
+            Direction::Positive => value >= thresholds.release_positive,
+        };
+
+        if held {
+            self
+        } else if value >= thresholds.engage_positive {
+            Direction::Positive
+        } else if value <= thresholds.engage_negative {
+            Direction::Negative
+        } else {
+            Direction::Neutral
+        }
+    }
+
+    /// Writes the directions of both axes to the D-pad buttons of `state`.
+    pub(crate) fn press_dpad(x: Direction, y: Direction, state: &mut State) {
+        state.press(Button::Left, x == Direction::Negative);
+        state.press(Button::Right, x == Direction::Positive);
+        state.press(Button::Up, y == Direction::Negative);
+        state.press(Button::Down, y == Direction::Positive);
+    }
+}
+
+/// Converts a centered analog stick into D-pad presses with hysteresis, so a value hovering around the
+/// threshold does not make the direction chatter. `inner` should not be larger than `outer`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DpadHysteresis {
+    inner: i16,
+    outer: i16,
+    x: Direction,
+    y: Direction,
+}
+
+impl DpadHysteresis {
+    pub fn new(inner: i16, outer: i16) -> Self {
+        Self {
+            inner,
+            outer,
+            x: Direction::Neutral,
+            y: Direction::Neutral,
+        }
+    }
+
+    /// Feeds the current axis values (positive x is right, positive y is down) and writes the committed
+    /// directions to the D-pad buttons of `state`.
+    pub fn update(&mut self, x: i16, y: i16, state: &mut State) {
+        let thresholds = AxisThresholds {
+            engage_positive: self.outer,
+            release_positive: self.inner,
+            engage_negative: self.outer.saturating_neg(),
+            release_negative: self.inner.saturating_neg(),
+        };
+
+        self.x = self.x.update(x, &thresholds);
+        self.y = self.y.update(y, &thresholds);
+
+        Direction::press_dpad(self.x, self.y, state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hovering_around_the_threshold_does_not_chatter() {
+        let mut dpad = DpadHysteresis::new(200, 500);
+        let mut state = State::default();
+
+        for (x, right) in [
+            (450, false),
+            (510, true),
+            (490, true),
+            (505, true),
+            (300, true),
+            (199, false),
+            (450, false),
+        ] {
+            dpad.update(x, 0, &mut state);
+            assert_eq!(state.is_right_pressed(), right, "x = {x}");
+            assert!(!state.is_left_pressed());
+        }
+    }
+
+    #[test]
+    fn extremes_map_to_each_direction() {
+        let mut dpad = DpadHysteresis::new(200, 500);
+        let mut state = State::default();
+
+        dpad.update(i16::MIN, i16::MAX, &mut state);
+        assert!(state.is_left_pressed() && state.is_down_pressed());
+        assert!(!state.is_right_pressed() && !state.is_up_pressed());
+
+        dpad.update(i16::MAX, i16::MIN, &mut state);
+        assert!(state.is_right_pressed() && state.is_up_pressed());
+        assert!(!state.is_left_pressed() && !state.is_down_pressed());
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{Button, State};
+
+/// Remembers the previous frame so single-shot actions can react to the frame a button changed on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FrameEdges {
+    previous: State,
+    current: State,
+}
+
+impl FrameEdges {
+    /// Moves on to the next frame with `state` as its input.
+    pub fn update(&mut self, state: State) {
+        self.previous = self.current;
+        self.current = state;
+    }
+
+    pub fn current(&self) -> State {
+        self.current
+    }
+
+    /// True only on the first frame `button` is held.
+    pub fn just_pressed(&self, button: Button) -> bool {
+        self.current.is_pressed(button) && !self.previous.is_pressed(button)
+    }
+
+    /// True only on the first frame after `button` was let go.
+    pub fn just_released(&self, button: Button) -> bool {
+        !self.current.is_pressed(button) && self.previous.is_pressed(button)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BUTTON_A;
+
+    fn pressed(mask: u16) -> State {
+        let mut state = State::default();
+        state.apply_mask(mask);
+        state
+    }
+
+    #[test]
+    fn edges_fire_once_per_hold_and_release() {
+        let mut edges = FrameEdges::default();
+        let held = pressed(BUTTON_A);
+
+        edges.update(held);
+        assert!(edges.just_pressed(Button::A));
+
+        edges.update(held);
+        assert!(!edges.just_pressed(Button::A));
+        assert!(!edges.just_released(Button::A));
+
+        edges.update(State::default());
+        assert!(edges.just_released(Button::A));
+
+        edges.update(State::default());
+        assert!(!edges.just_released(Button::A));
+    }
+}
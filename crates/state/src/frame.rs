@@ -0,0 +1,72 @@
+/// A frame number, i.e. one latch pulse of the console (about 16.67 ms on NTSC).
+/// Frame numbers wrap around, so compare them with `since` rather than `<`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Frame(pub u32);
+
+impl Frame {
+    pub fn next(self) -> Frame {
+        Frame(self.0.wrapping_add(1))
+    }
+
+    /// Number of frames elapsed from `earlier` to `self`.
+    pub fn since(self, earlier: Frame) -> u32 {
+        self.0.wrapping_sub(earlier.0)
+    }
+}
+
+/// Something that knows the current frame, e.g. a latch counter on target or `ManualFrames` on the host.
+pub trait FrameSource {
+    fn frame(&self) -> Frame;
+}
+
+/// A frame source that only moves when told to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ManualFrames {
+    frame: Frame,
+}
+
+impl ManualFrames {
+    pub fn new(frame: Frame) -> Self {
+        Self { frame }
+    }
+
+    pub fn advance(&mut self) {
+        self.frame = self.frame.next();
+    }
+
+    pub fn advance_by(&mut self, frames: u32) {
+        self.frame = Frame(self.frame.0.wrapping_add(frames));
+    }
+}
+
+impl FrameSource for ManualFrames {
+    fn frame(&self) -> Frame {
+        self.frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manual_frames_only_move_when_advanced() {
+        let mut frames = ManualFrames::new(Frame(10));
+        assert_eq!(frames.frame(), Frame(10));
+
+        frames.advance();
+        frames.advance_by(4);
+
+        assert_eq!(frames.frame(), Frame(15));
+        assert_eq!(frames.frame().since(Frame(10)), 5);
+    }
+
+    #[test]
+    fn since_survives_wrap_around() {
+        let mut frames = ManualFrames::new(Frame(u32::MAX - 1));
+        frames.advance_by(3);
+
+        assert_eq!(frames.frame(), Frame(1));
+        assert_eq!(frames.frame().since(Frame(u32::MAX - 1)), 3);
+    }
+}
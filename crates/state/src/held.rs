@@ -0,0 +1,100 @@
+use crate::{offset, Button, State, WireError};
+
+/// A state plus, per button, the number of consecutive frames it has been held, saturating at 255.
+///
+/// Encoded as the little-endian state word followed by the twelve counters in clock-out order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StateWithHold {
+    state: State,
+    held: [u8; 12],
+}
+
+impl StateWithHold {
+    pub const ENCODED_LEN: usize = 14;
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Frames `button` has been held, 0 if it is released.
+    pub fn held_for(&self, button: Button) -> u8 {
+        self.held[offset(button) as usize]
+    }
+
+    /// Records the next frame. Held buttons count up and released buttons reset to 0.
+    pub fn update(&mut self, state: State) {
+        for button in Button::ALL {
+            let held = &mut self.held[offset(button) as usize];
+            *held = match state.is_pressed(button) {
+                true => held.saturating_add(1),
+                false => 0,
+            };
+        }
+
+        self.state = state;
+    }
+
+    /// Writes the payload into the start of `buf` and returns the number of bytes written.
+    pub fn encode(&self, buf: &mut [u8]) -> Result<usize, WireError> {
+        let buf = buf
+            .get_mut(..Self::ENCODED_LEN)
+            .ok_or(WireError::BufferTooSmall)?;
+
+        buf[..2].copy_from_slice(&self.state.value.to_le_bytes());
+        buf[2..].copy_from_slice(&self.held);
+
+        Ok(Self::ENCODED_LEN)
+    }
+
+    pub fn decode(buf: &[u8]) -> Result<Self, WireError> {
+        let buf = buf
+            .get(..Self::ENCODED_LEN)
+            .ok_or(WireError::BufferTooSmall)?;
+
+        let mut held = [0; 12];
+        held.copy_from_slice(&buf[2..]);
+
+        Ok(Self {
+            state: State::from_u16(u16::from_le_bytes([buf[0], buf[1]])),
+            held,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BUTTON_A, BUTTON_L};
+
+    fn pressed(mask: u16) -> State {
+        let mut state = State::default();
+        state.apply_mask(mask);
+        state
+    }
+
+    #[test]
+    fn round_trip_with_counters() {
+        let mut held = StateWithHold::default();
+        held.update(pressed(BUTTON_A));
+        held.update(pressed(BUTTON_A | BUTTON_L));
+        let mut buf = [0; StateWithHold::ENCODED_LEN];
+
+        assert_eq!(held.encode(&mut buf), Ok(StateWithHold::ENCODED_LEN));
+        assert_eq!(buf[2 + offset(Button::A) as usize], 2);
+        assert_eq!(buf[2 + offset(Button::L) as usize], 1);
+        assert_eq!(StateWithHold::decode(&buf), Ok(held));
+    }
+
+    #[test]
+    fn counters_saturate_at_255() {
+        let mut held = StateWithHold::default();
+        for _ in 0..300 {
+            held.update(pressed(BUTTON_A));
+        }
+
+        assert_eq!(held.held_for(Button::A), 255);
+
+        held.update(State::default());
+        assert_eq!(held.held_for(Button::A), 0);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::State;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HoldMode {
+    /// Pass the live input through.
+    #[default]
+    Live,
+    /// Keep reporting the buttons of the last live frame.
+    HoldLast,
+    /// Report every button as released.
+    ForceNeutral,
+}
+
+/// Decides per frame whether the console sees the live input, the last live input or a neutral pad,
+/// e.g. while the host has a menu open.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HoldFilter {
+    mode: HoldMode,
+    last: State,
+}
+
+impl HoldFilter {
+    pub fn mode(&self) -> HoldMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: HoldMode) {
+        self.mode = mode;
+    }
+
+    /// Returns the state to report for this frame. Only the buttons are affected, the cycle of `live` is kept.
+    pub fn apply(&mut self, live: State) -> State {
+        let mut state = live;
+
+        match self.mode {
+            HoldMode::Live => self.last = live,
+            HoldMode::HoldLast => state.apply_mask(self.last.pressed_mask()),
+            HoldMode::ForceNeutral => state.apply_mask(0),
+        }
+
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BUTTON_A, BUTTON_B};
+
+    fn pressed(mask: u16) -> State {
+        let mut state = State::default();
+        state.apply_mask(mask);
+        state
+    }
+
+    #[test]
+    fn hold_last_repeats_the_last_live_frame() {
+        let mut filter = HoldFilter::default();
+        filter.apply(pressed(BUTTON_A));
+        filter.set_mode(HoldMode::HoldLast);
+
+        let held = filter.apply(pressed(BUTTON_B));
+
+        assert_eq!(held.pressed_mask(), BUTTON_A);
+
+        filter.set_mode(HoldMode::Live);
+        assert_eq!(filter.apply(pressed(BUTTON_B)).pressed_mask(), BUTTON_B);
+    }
+
+    #[test]
+    fn force_neutral_releases_everything() {
+        let mut filter = HoldFilter::default();
+        filter.set_mode(HoldMode::ForceNeutral);
+
+        let mut live = pressed(BUTTON_A | BUTTON_B);
+        live.next();
+        let neutral = filter.apply(live);
+
+        assert_eq!(neutral.pressed_mask(), 0);
+        assert_eq!(neutral.cycle(), 1);
+    }
+}
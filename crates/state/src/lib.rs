@@ -22,6 +22,55 @@ Bits Offset Length Name        Values
 
 use core::fmt::Display;
 
+use embedded_hal::digital::{InputPin, PinState};
+
+mod atomic;
+mod calibration;
+mod combined;
+mod delta;
+mod diff;
+mod dpad;
+mod edges;
+mod frame;
+mod held;
+mod hold;
+mod macro_player;
+mod mapping;
+mod mouse;
+mod ops;
+mod output;
+mod peripheral;
+mod rate;
+mod rollback;
+mod shift;
+mod tas;
+mod timestamp;
+mod watchdog;
+mod wire;
+
+pub use atomic::AtomicState;
+pub use calibration::{AxisRange, AxisThresholds, CalibratedDpad, Calibration};
+pub use combined::CombinedState;
+pub use delta::{DeltaDecoder, DeltaEncoder, StateDelta};
+pub use diff::{ButtonEvent, StateDiff};
+pub use dpad::DpadHysteresis;
+pub use edges::FrameEdges;
+pub use frame::{Frame, FrameSource, ManualFrames};
+pub use held::StateWithHold;
+pub use hold::{HoldFilter, HoldMode};
+pub use macro_player::MacroPlayer;
+pub use mapping::{Mapping, MAPPING_LEN};
+pub use mouse::{Sensitivity, SnesMouse};
+pub use output::OutputState;
+pub use peripheral::Peripheral;
+pub use rate::RateLimiter;
+pub use rollback::RollbackBuffer;
+pub use shift::{BitOrder, Presence, WireConfig};
+pub use tas::TasLog;
+pub use timestamp::TimestampedState;
+pub use watchdog::{SoftResetWatchdog, SOFT_RESET_COMBO};
+pub use wire::{WireError, STATE_WIRE_VERSION};
+
 const B_OFFSET: u16 = 0;
 const Y_OFFSET: u16 = 1;
 const SELECT_OFFSET: u16 = 2;
@@ -38,37 +87,250 @@ const CYCLE_OFFSET: u16 = 12;
 
 const CYCLE_LEN: u16 = 4;
 
+const CYCLES: u16 = 16;
+
+const BUTTONS_MASK: u16 = 0b0000_1111_1111_1111;
+
+/// Generates, for each `offset => mask, setter, getter` entry, a public `BUTTON_*` mask constant for
+/// `State::any_pressed`/`State::all_pressed`, a setter writing the raw wire bit (`true` = high = not pressed)
+/// and a getter returning `true` while the button is held down.
+macro_rules! bitfield {
+    ($($offset:ident => $mask:ident, $set:ident, $get:ident;)*) => {
+        $(
+            pub const $mask: u16 = 1 << $offset;
+        )*
+
+        impl State {
+            $(
+                /// Writes the raw wire bit, so `true` releases the button and `false` presses it.
+                /// This is the opposite of the matching `is_*_pressed` getter and of `State::press`.
+                pub fn $set(&mut self, value: bool) {
+                    self.write_bit(value, $offset);
+                }
+
+                pub fn $get(&self) -> bool {
+                    !self.read_bit($offset)
+                }
+            )*
+        }
+    };
+}
+
+bitfield! {
+    B_OFFSET => BUTTON_B, set_b, is_b_pressed;
+    Y_OFFSET => BUTTON_Y, set_y, is_y_pressed;
+    SELECT_OFFSET => BUTTON_SELECT, set_select, is_select_pressed;
+    START_OFFSET => BUTTON_START, set_start, is_start_pressed;
+    UP_OFFSET => BUTTON_UP, set_up, is_up_pressed;
+    DOWN_OFFSET => BUTTON_DOWN, set_down, is_down_pressed;
+    LEFT_OFFSET => BUTTON_LEFT, set_left, is_left_pressed;
+    RIGHT_OFFSET => BUTTON_RIGHT, set_right, is_right_pressed;
+    A_OFFSET => BUTTON_A, set_a, is_a_pressed;
+    X_OFFSET => BUTTON_X, set_x, is_x_pressed;
+    L_OFFSET => BUTTON_L, set_l, is_l_pressed;
+    R_OFFSET => BUTTON_R, set_r, is_r_pressed;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Button {
+    B,
+    Y,
+    Select,
+    Start,
+    Up,
+    Down,
+    Left,
+    Right,
+    A,
+    X,
+    L,
+    R,
+}
+
+impl Button {
+    /// All buttons in clock-out order, which is also the order of their bit offsets.
+    pub const ALL: [Button; 12] = [
+        Button::B,
+        Button::Y,
+        Button::Select,
+        Button::Start,
+        Button::Up,
+        Button::Down,
+        Button::Left,
+        Button::Right,
+        Button::A,
+        Button::X,
+        Button::L,
+        Button::R,
+    ];
+}
+
+/// Bit offset of `button` in the state word, matching the layout table at the top of this file.
+pub const fn offset(button: Button) -> u16 {
+    match button {
+        Button::B => B_OFFSET,
+        Button::Y => Y_OFFSET,
+        Button::Select => SELECT_OFFSET,
+        Button::Start => START_OFFSET,
+        Button::Up => UP_OFFSET,
+        Button::Down => DOWN_OFFSET,
+        Button::Left => LEFT_OFFSET,
+        Button::Right => RIGHT_OFFSET,
+        Button::A => A_OFFSET,
+        Button::X => X_OFFSET,
+        Button::L => L_OFFSET,
+        Button::R => R_OFFSET,
+    }
+}
+
+/// Buttons to hold down in `State::from_buttons`, `true` meaning pressed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Buttons {
+    pub b: bool,
+    pub y: bool,
+    pub select: bool,
+    pub start: bool,
+    pub up: bool,
+    pub down: bool,
+    pub left: bool,
+    pub right: bool,
+    pub a: bool,
+    pub x: bool,
+    pub l: bool,
+    pub r: bool,
+}
+
+/// Maps every physical button to the logical button it should be reported as.
+/// The table is indexed by the bit offset of the physical button, so `Button::ALL` is the identity remap.
+pub type Remap = [Button; 12];
+
+#[derive(Clone, Copy, Debug, Eq)]
 pub struct State {
     value: u16,
+    /// Set once all 16 bits were clocked out. The cycle bits can only count to 15, so this is what lets
+    /// `cycle` report 16 instead of wrapping around to 0. It is a transient detail of the clock-out and is
+    /// not part of the word, so it is ignored by equality and validity checks.
+    shifted_out: bool,
 }
 
 impl Default for State {
     fn default() -> Self {
-        Self {
-            value: 0b0000_1111_1111_1111,
-        }
+        Self::from_u16(0b0000_1111_1111_1111)
+    }
+}
+
+impl PartialEq for State {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
     }
 }
 
 impl Display for State {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{:16b}", self.value)
+        write!(f, "{:016b}", self.value)
     }
 }
 
 impl State {
+    /// Rebuilds a state from its packed 16-bit word, cycle bits included.
+    pub fn from_u16(value: u16) -> Self {
+        Self {
+            value,
+            shifted_out: false,
+        }
+    }
+
+    /// The packed 16-bit word in the layout described at the top of this file.
+    /// A fully shifted-out state packs with cycle 0, as the word cannot hold cycle 16.
+    pub fn to_u16(&self) -> u16 {
+        self.value
+    }
+
+    /// A state at cycle 0 with the buttons set in `buttons` held down.
+    pub fn from_buttons(buttons: Buttons) -> Self {
+        let pressed = [
+            buttons.b,
+            buttons.y,
+            buttons.select,
+            buttons.start,
+            buttons.up,
+            buttons.down,
+            buttons.left,
+            buttons.right,
+            buttons.a,
+            buttons.x,
+            buttons.l,
+            buttons.r,
+        ];
+
+        let mut state = Self::default();
+        for (button, pressed) in Button::ALL.into_iter().zip(pressed) {
+            state.press(button, pressed);
+        }
+
+        state
+    }
+
+    /// A state with every button held down, e.g. as a test fixture.
+    pub fn all_buttons_pressed() -> Self {
+        Self::with_buttons_pressed(BUTTONS_MASK)
+    }
+
+    /// A state at cycle 0 with exactly the buttons in `mask` (`BUTTON_*` layout) held down.
+    pub fn with_buttons_pressed(mask: u16) -> Self {
+        let mut state = Self::default();
+        state.apply_mask(mask);
+        state
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> bool {
+        self.next_as(Peripheral::Controller)
+    }
+
+    /// Like `next`, but clocks 13 to 16 carry the ID signature of `peripheral` instead of the standard pad's.
+    /// Once all 16 bits are out the cycle stays at 16 and the line is held low until `reset_cycle`.
+    pub fn next_as(&mut self, peripheral: Peripheral) -> bool {
+        let level = self.level_as(peripheral);
         let cycle = self.cycle();
-        self.set_cycle(cycle + 1);
 
-        match cycle {
-            x if x < 12 => self.read_bit(cycle),
-            _ => true,
+        if cycle < CYCLES {
+            self.set_cycle(cycle + 1);
         }
+
+        level
+    }
+
+    /// Data line level for the current cycle without advancing it.
+    /// The line is open-drain and active-low, so a pressed button pulls it low.
+    pub fn data_level(&self) -> PinState {
+        self.level().into()
     }
 
+    fn level(&self) -> bool {
+        self.level_as(Peripheral::Controller)
+    }
+
+    fn level_as(&self, peripheral: Peripheral) -> bool {
+        match self.cycle() {
+            x if x < 12 => self.read_bit(x),
+            x if x < CYCLES => peripheral.signature() >> (CYCLES - 1 - x) & 1 == 0,
+            _ => false,
+        }
+    }
+
+    /// Number of bits clocked out since the last `reset_cycle`, from 0 to 16.
     pub fn cycle(&self) -> u16 {
-        self.read(CYCLE_LEN, CYCLE_OFFSET)
+        if self.shifted_out {
+            CYCLES
+        } else {
+            self.read(CYCLE_LEN, CYCLE_OFFSET)
+        }
+    }
+
+    /// Number of clock cycles left before all 16 bits are shifted out, 0 once they are.
+    pub fn cycle_remaining(&self) -> u16 {
+        CYCLES.saturating_sub(self.cycle())
     }
 
     fn read_bit(&self, offset: u16) -> bool {
@@ -80,52 +342,77 @@ impl State {
         self.value >> offset << (N - len) >> (N - len)
     }
 
-    pub fn set_b(&mut self, value: bool) {
-        self.write_bit(value.into(), B_OFFSET);
-    }
+    /// Returns a new state with every pressed physical button relabeled according to `remap`.
+    /// The cycle is carried over unchanged.
+    pub fn remapped(&self, remap: &Remap) -> State {
+        let mut state = *self;
+        state.value |= BUTTONS_MASK;
 
-    pub fn set_y(&mut self, value: bool) {
-        self.write_bit(value.into(), Y_OFFSET);
-    }
+        for button in Button::ALL {
+            if self.is_pressed(button) {
+                state.press(remap[offset(button) as usize], true);
+            }
+        }
 
-    pub fn set_select(&mut self, value: bool) {
-        self.write_bit(value.into(), SELECT_OFFSET);
+        state
     }
 
-    pub fn set_start(&mut self, value: bool) {
-        self.write_bit(value.into(), START_OFFSET);
+    /// Samples one active-low input pin per button, indexed in `Button::ALL` order.
+    /// A low pin is recorded as pressed. If any pin read fails the state is left untouched.
+    pub fn sample_from_pins<P: InputPin>(&mut self, pins: &mut [P; 12]) -> Result<(), P::Error> {
+        let mut sampled = *self;
+
+        for (button, pin) in Button::ALL.into_iter().zip(pins.iter_mut()) {
+            sampled.press(button, pin.is_low()?);
+        }
+
+        *self = sampled;
+        Ok(())
     }
 
-    pub fn set_up(&mut self, value: bool) {
-        self.write_bit(value.into(), UP_OFFSET);
+    /// Returns true if at least one of the buttons in `mask` is pressed.
+    pub fn any_pressed(&self, mask: u16) -> bool {
+        self.pressed_mask() & mask != 0
     }
 
-    pub fn set_down(&mut self, value: bool) {
-        self.write_bit(value.into(), DOWN_OFFSET);
+    /// Returns true if every button in `mask` is pressed. Bits outside the button range are ignored.
+    pub fn all_pressed(&self, mask: u16) -> bool {
+        let mask = mask & BUTTONS_MASK;
+        self.pressed_mask() & mask == mask
     }
 
-    pub fn set_left(&mut self, value: bool) {
-        self.write_bit(value.into(), LEFT_OFFSET);
+    /// Applies `(button, pressed)` changes in order, so a later entry for the same button wins.
+    pub fn apply_changes(&mut self, changes: &[(Button, bool)]) {
+        for &(button, pressed) in changes {
+            self.press(button, pressed);
+        }
     }
 
-    pub fn set_right(&mut self, value: bool) {
-        self.write_bit(value.into(), RIGHT_OFFSET);
+    /// Returns true if the word is a plausible state at rest, e.g. after reading it from untrusted bytes.
+    /// Every bit is either a button or part of the cycle, so only the cycle can be out of place: it must be 0.
+    /// A fully shifted-out state is judged by its word alone, like any other.
+    pub fn is_valid(&self) -> bool {
+        self.read(CYCLE_LEN, CYCLE_OFFSET) == 0
     }
 
-    pub fn set_a(&mut self, value: bool) {
-        self.write_bit(value.into(), A_OFFSET);
+    /// Sets all twelve buttons at once from a mask built from the `BUTTON_*` constants.
+    /// Buttons in the mask are pressed, all others are released and the cycle is left untouched.
+    pub fn apply_mask(&mut self, pressed_mask: u16) {
+        self.value = (self.value & !BUTTONS_MASK) | (!pressed_mask & BUTTONS_MASK);
     }
 
-    pub fn set_x(&mut self, value: bool) {
-        self.write_bit(value.into(), X_OFFSET);
+    /// The button bits with pressed buttons set, i.e. the inverse of the wire encoding.
+    fn pressed_mask(&self) -> u16 {
+        !self.value & BUTTONS_MASK
     }
 
-    pub fn set_l(&mut self, value: bool) {
-        self.write_bit(value.into(), L_OFFSET);
+    /// Returns true while `button` is held down, the same as the matching `is_*_pressed` getter.
+    pub fn is_pressed(&self, button: Button) -> bool {
+        !self.read_bit(offset(button))
     }
 
-    pub fn set_r(&mut self, value: bool) {
-        self.write_bit(value.into(), R_OFFSET);
+    fn press(&mut self, button: Button, pressed: bool) {
+        self.write_bit(!pressed, offset(button));
     }
 
     pub fn reset_cycle(&mut self) {
@@ -135,7 +422,9 @@ impl State {
     /// Sets the cycle to the given value.
     /// When the value exceeds 15 it will be automatically cut off because the cycle bits are located at the end of the value
     /// When writing the value it is shifted by 12 bits to the left cutting off the fifth bit making it impossible to exceed 15
+    /// A value of 16 or more is remembered separately as fully shifted out.
     fn set_cycle(&mut self, value: u16) {
+        self.shifted_out = value >= CYCLES;
         self.write(value, CYCLE_LEN, CYCLE_OFFSET);
     }
 
@@ -145,8 +434,517 @@ impl State {
 
     fn write(&mut self, value: u16, len: u16, offset: u16) {
         let mask = u16::MAX >> (u16::BITS as u16 - len);
-        let value = (value as u16) & mask;
+        let value = value & mask;
         self.value &= !(mask << offset);
         self.value |= value << offset;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::format;
+
+    use super::*;
+
+    type Setter = fn(&mut State, bool);
+
+    /// A state with the buttons of `setters` held down. Setters write the raw bit, so `false` presses.
+    fn pressed(setters: &[Setter]) -> State {
+        let mut state = State::default();
+        for set in setters {
+            set(&mut state, false);
+        }
+        state
+    }
+
+    /// Clocks a full frame out the way the console reads it: bit `n` is the data line level on clock `n + 1`.
+    fn capture(state: &mut State) -> u16 {
+        state.reset_cycle();
+
+        (0..16).fold(0, |capture, clock| capture | (state.next() as u16) << clock)
+    }
+
+    #[test]
+    fn clock_out_matches_snes_capture() {
+        let cases: [(&[Setter], u16); 6] = [
+            (&[], 0b1111_1111_1111_1111),
+            (&[State::set_b], 0b1111_1111_1111_1110),
+            (&[State::set_start, State::set_up], 0b1111_1111_1110_0111),
+            (&[State::set_a, State::set_right], 0b1111_1110_0111_1111),
+            (
+                &[
+                    State::set_y,
+                    State::set_select,
+                    State::set_x,
+                    State::set_l,
+                    State::set_r,
+                ],
+                0b1111_0001_1111_1001,
+            ),
+            (
+                &[
+                    State::set_b,
+                    State::set_y,
+                    State::set_select,
+                    State::set_start,
+                    State::set_up,
+                    State::set_down,
+                    State::set_left,
+                    State::set_right,
+                    State::set_a,
+                    State::set_x,
+                    State::set_l,
+                    State::set_r,
+                ],
+                0b1111_0000_0000_0000,
+            ),
+        ];
+
+        for (setters, expected) in cases {
+            let mut state = pressed(setters);
+            assert_eq!(capture(&mut state), expected, "{expected:016b}");
+        }
+    }
+
+    #[test]
+    fn clock_out_restarts_after_reset_cycle() {
+        let mut state = pressed(&[State::set_b]);
+        state.next();
+        state.next();
+
+        assert_eq!(capture(&mut state), 0b1111_1111_1111_1110);
+    }
+
+    #[test]
+    fn identity_remap_changes_nothing() {
+        let mut state = State::default();
+        state.press(Button::A, true);
+        state.press(Button::L, true);
+        state.press(Button::Down, true);
+        state.next();
+
+        assert_eq!(state.remapped(&Button::ALL).value, state.value);
+    }
+
+    #[test]
+    fn remap_swaps_a_and_b() {
+        let mut remap = Button::ALL;
+        remap[offset(Button::A) as usize] = Button::B;
+        remap[offset(Button::B) as usize] = Button::A;
+
+        let mut state = State::default();
+        state.press(Button::A, true);
+        state.press(Button::Start, true);
+        let remapped = state.remapped(&remap);
+
+        assert!(remapped.is_pressed(Button::B));
+        assert!(!remapped.is_pressed(Button::A));
+        assert!(remapped.is_pressed(Button::Start));
+        assert_eq!(remapped.remapped(&remap).value, state.value);
+    }
+
+    #[derive(Debug)]
+    struct PinFault;
+
+    impl embedded_hal::digital::Error for PinFault {
+        fn kind(&self) -> embedded_hal::digital::ErrorKind {
+            embedded_hal::digital::ErrorKind::Other
+        }
+    }
+
+    struct MockPin {
+        low: bool,
+        fail: bool,
+    }
+
+    impl embedded_hal::digital::ErrorType for MockPin {
+        type Error = PinFault;
+    }
+
+    impl InputPin for MockPin {
+        fn is_high(&mut self) -> Result<bool, PinFault> {
+            self.is_low().map(|low| !low)
+        }
+
+        fn is_low(&mut self) -> Result<bool, PinFault> {
+            if self.fail {
+                Err(PinFault)
+            } else {
+                Ok(self.low)
+            }
+        }
+    }
+
+    /// One mock pin per button in `Button::ALL` order, low for the buttons in `low`.
+    fn pins(low: &[Button]) -> [MockPin; 12] {
+        Button::ALL.map(|button| MockPin {
+            low: low.contains(&button),
+            fail: false,
+        })
+    }
+
+    #[test]
+    fn sample_records_low_pins_as_pressed() {
+        let mut pins = pins(&[Button::B, Button::Up, Button::R]);
+        let mut state = State::default();
+        state.press(Button::A, true);
+
+        state.sample_from_pins(&mut pins).unwrap();
+
+        for button in Button::ALL {
+            let expected = matches!(button, Button::B | Button::Up | Button::R);
+            assert_eq!(state.is_pressed(button), expected, "{button:?}");
+        }
+    }
+
+    #[test]
+    fn failed_sample_leaves_state_untouched() {
+        let mut pins = pins(&[Button::B, Button::Up]);
+        pins[11].fail = true;
+        let mut state = State::default();
+        state.press(Button::A, true);
+        let before = state.value;
+
+        assert!(state.sample_from_pins(&mut pins).is_err());
+        assert_eq!(state.value, before);
+    }
+
+    /// A state at cycle 0 with `buttons` held down.
+    fn holding(buttons: &[Button]) -> State {
+        let mut state = State::default();
+        for &button in buttons {
+            state.press(button, true);
+        }
+        state
+    }
+
+    #[test]
+    fn mask_queries_with_single_bit() {
+        let state = holding(&[Button::X]);
+
+        assert!(state.any_pressed(BUTTON_X));
+        assert!(state.all_pressed(BUTTON_X));
+        assert!(!state.any_pressed(BUTTON_Y));
+        assert!(!state.all_pressed(BUTTON_Y));
+    }
+
+    #[test]
+    fn mask_queries_with_several_bits() {
+        let state = holding(&[Button::L, Button::R]);
+
+        assert!(state.all_pressed(BUTTON_L | BUTTON_R));
+        assert!(state.any_pressed(BUTTON_L | BUTTON_START));
+        assert!(!state.all_pressed(BUTTON_L | BUTTON_START));
+        assert!(!state.any_pressed(BUTTON_SELECT | BUTTON_START));
+        assert!(state.all_pressed(BUTTON_L | BUTTON_R | !BUTTONS_MASK));
+    }
+
+    #[test]
+    fn apply_mask_stores_pressed_as_low_bits() {
+        let mut state = holding(&[Button::Select]);
+
+        state.apply_mask(BUTTON_A | BUTTON_LEFT);
+
+        assert_eq!(
+            state.value & BUTTONS_MASK,
+            !(BUTTON_A | BUTTON_LEFT) & BUTTONS_MASK
+        );
+        assert_eq!(state.pressed_mask(), BUTTON_A | BUTTON_LEFT);
+    }
+
+    #[test]
+    fn apply_mask_preserves_cycle() {
+        let mut state = State::default();
+        for _ in 0..5 {
+            state.next();
+        }
+
+        state.apply_mask(BUTTON_R);
+
+        assert_eq!(state.cycle(), 5);
+        assert!(state.is_pressed(Button::R));
+    }
+
+    #[test]
+    fn offsets_match_layout_table() {
+        let table = [
+            (Button::B, 0),
+            (Button::Y, 1),
+            (Button::Select, 2),
+            (Button::Start, 3),
+            (Button::Up, 4),
+            (Button::Down, 5),
+            (Button::Left, 6),
+            (Button::Right, 7),
+            (Button::A, 8),
+            (Button::X, 9),
+            (Button::L, 10),
+            (Button::R, 11),
+        ];
+
+        for (button, expected) in table {
+            assert_eq!(offset(button), expected, "{button:?}");
+        }
+    }
+
+    #[test]
+    fn words_at_rest_are_valid() {
+        assert!(State::default().is_valid());
+        assert!(State::from_u16(0x0A5F).is_valid());
+    }
+
+    #[test]
+    fn words_mid_cycle_are_invalid() {
+        assert!(!State::from_u16(0x3FFF).is_valid());
+
+        let mut state = State::default();
+        state.next();
+        assert!(!state.is_valid());
+    }
+
+    type Accessors = (fn(&mut State, bool), fn(&State) -> bool, u16, Button);
+
+    const ACCESSORS: [Accessors; 12] = [
+        (State::set_b, State::is_b_pressed, BUTTON_B, Button::B),
+        (State::set_y, State::is_y_pressed, BUTTON_Y, Button::Y),
+        (
+            State::set_select,
+            State::is_select_pressed,
+            BUTTON_SELECT,
+            Button::Select,
+        ),
+        (
+            State::set_start,
+            State::is_start_pressed,
+            BUTTON_START,
+            Button::Start,
+        ),
+        (State::set_up, State::is_up_pressed, BUTTON_UP, Button::Up),
+        (
+            State::set_down,
+            State::is_down_pressed,
+            BUTTON_DOWN,
+            Button::Down,
+        ),
+        (
+            State::set_left,
+            State::is_left_pressed,
+            BUTTON_LEFT,
+            Button::Left,
+        ),
+        (
+            State::set_right,
+            State::is_right_pressed,
+            BUTTON_RIGHT,
+            Button::Right,
+        ),
+        (State::set_a, State::is_a_pressed, BUTTON_A, Button::A),
+        (State::set_x, State::is_x_pressed, BUTTON_X, Button::X),
+        (State::set_l, State::is_l_pressed, BUTTON_L, Button::L),
+        (State::set_r, State::is_r_pressed, BUTTON_R, Button::R),
+    ];
+
+    #[test]
+    fn setter_false_presses_the_button() {
+        for (set, get, mask, button) in ACCESSORS {
+            let mut state = State::default();
+            set(&mut state, false);
+
+            assert_eq!(state.value, BUTTONS_MASK & !mask, "{button:?}");
+            assert!(get(&state), "{button:?}");
+            assert!(state.is_pressed(button), "{button:?}");
+            assert_eq!(state.pressed_mask(), mask, "{button:?}");
+        }
+    }
+
+    #[test]
+    fn setter_true_releases_the_button() {
+        for (set, get, mask, button) in ACCESSORS {
+            let mut state = State::default();
+            state.apply_mask(BUTTONS_MASK);
+            set(&mut state, true);
+
+            assert_eq!(state.value, mask, "{button:?}");
+            assert!(!get(&state), "{button:?}");
+            assert!(!state.is_pressed(button), "{button:?}");
+        }
+    }
+
+    #[test]
+    fn masks_match_offsets() {
+        for (_, _, mask, button) in ACCESSORS {
+            assert_eq!(mask, 1 << offset(button), "{button:?}");
+        }
+    }
+
+    #[test]
+    fn data_level_follows_a_full_cycle() {
+        let mut state = holding(&[Button::B, Button::R]);
+
+        for clock in 0..16 {
+            let expected = if clock == 0 || clock == 11 {
+                PinState::Low
+            } else {
+                PinState::High
+            };
+
+            assert_eq!(state.data_level(), expected, "clock {}", clock + 1);
+            assert_eq!(PinState::from(state.next()), expected);
+        }
+    }
+
+    #[test]
+    fn data_level_does_not_advance() {
+        let state = holding(&[Button::B]);
+
+        assert_eq!(state.data_level(), PinState::Low);
+        assert_eq!(state.data_level(), PinState::Low);
+        assert_eq!(state.cycle(), 0);
+    }
+
+    #[test]
+    fn all_buttons_pressed_reports_every_getter() {
+        let state = State::all_buttons_pressed();
+
+        assert_eq!(state.cycle(), 0);
+        assert_eq!(state.pressed_mask(), BUTTONS_MASK);
+        assert!(state.is_b_pressed() && state.is_y_pressed() && state.is_select_pressed());
+        assert!(state.is_start_pressed() && state.is_up_pressed() && state.is_down_pressed());
+        assert!(state.is_left_pressed() && state.is_right_pressed() && state.is_a_pressed());
+        assert!(state.is_x_pressed() && state.is_l_pressed() && state.is_r_pressed());
+    }
+
+    #[test]
+    fn with_buttons_pressed_ignores_cycle_bits() {
+        let state = State::with_buttons_pressed(BUTTON_Y | 0xF000);
+
+        assert_eq!(state.cycle(), 0);
+        assert_eq!(state.pressed_mask(), BUTTON_Y);
+    }
+
+    #[test]
+    fn cycle_remaining_counts_down_to_zero() {
+        let mut state = State::default();
+
+        for clocked in 0..=CYCLES {
+            assert_eq!(state.cycle(), clocked);
+            assert_eq!(state.cycle_remaining(), CYCLES - clocked);
+            state.next();
+        }
+    }
+
+    #[test]
+    fn cycle_stops_after_the_sixteenth_bit() {
+        let mut state = holding(&[Button::B]);
+        for _ in 0..CYCLES {
+            state.next();
+        }
+
+        for _ in 0..3 {
+            assert!(!state.next(), "line is held low past clock 16");
+            assert_eq!(state.cycle(), CYCLES);
+            assert_eq!(state.cycle_remaining(), 0);
+        }
+
+        state.reset_cycle();
+        assert_eq!(state.cycle_remaining(), CYCLES);
+        assert!(!state.next(), "B is clocked out again");
+    }
+
+    #[test]
+    fn shifted_out_state_still_equals_its_word() {
+        let mut state = holding(&[Button::A]);
+        for _ in 0..CYCLES {
+            state.next();
+        }
+
+        assert_eq!(state.cycle(), CYCLES);
+        assert!(state.is_valid());
+        assert_eq!(State::from_u16(state.to_u16()), state);
+        assert_eq!(state, holding(&[Button::A]));
+    }
+
+    #[test]
+    fn apply_changes_in_order() {
+        let mut state = State::with_buttons_pressed(BUTTON_Y | BUTTON_L);
+
+        state.apply_changes(&[
+            (Button::A, true),
+            (Button::Y, false),
+            (Button::Up, true),
+            (Button::A, false),
+            (Button::A, true),
+            (Button::Select, false),
+        ]);
+
+        assert_eq!(state.pressed_mask(), BUTTON_A | BUTTON_UP | BUTTON_L);
+    }
+
+    #[test]
+    fn display_is_sixteen_zero_padded_digits() {
+        assert_eq!(format!("{}", State::default()), "0000111111111111");
+        assert_eq!(
+            format!("{}", State::with_buttons_pressed(BUTTON_B)),
+            "0000111111111110"
+        );
+    }
+
+    #[test]
+    fn from_buttons_presses_only_the_named_buttons() {
+        let state = State::from_buttons(Buttons {
+            a: true,
+            start: true,
+            ..Default::default()
+        });
+
+        assert_eq!(state.cycle(), 0);
+        assert_eq!(state.pressed_mask(), BUTTON_A | BUTTON_START);
+        assert_eq!(state, State::with_buttons_pressed(BUTTON_A | BUTTON_START));
+    }
+
+    #[test]
+    fn from_buttons_covers_every_field() {
+        let all = Buttons {
+            b: true,
+            y: true,
+            select: true,
+            start: true,
+            up: true,
+            down: true,
+            left: true,
+            right: true,
+            a: true,
+            x: true,
+            l: true,
+            r: true,
+        };
+
+        assert_eq!(State::from_buttons(Buttons::default()), State::default());
+        assert_eq!(State::from_buttons(all), State::all_buttons_pressed());
+        assert_eq!(State::from_buttons(all).cycle(), 0);
+    }
+
+    #[test]
+    fn u16_round_trip_keeps_every_bit() {
+        for value in (0..=u16::MAX).step_by(7).chain([0xF000, 0x7ABC, u16::MAX]) {
+            let state = State::from_u16(value);
+
+            assert_eq!(state.to_u16(), value);
+            assert_eq!(state.cycle(), value >> CYCLE_OFFSET);
+            assert_eq!(State::from_u16(state.to_u16()), state);
+        }
+    }
+
+    #[test]
+    fn u16_round_trip_after_a_full_clock_out() {
+        let mut state = State::with_buttons_pressed(BUTTON_X);
+        for _ in 0..CYCLES {
+            state.next();
+        }
+
+        assert_eq!(state.to_u16() >> CYCLE_OFFSET, 0);
+        assert_eq!(State::from_u16(state.to_u16()), state);
+    }
+}
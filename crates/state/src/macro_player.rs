@@ -0,0 +1,105 @@
+use crate::State;
+
+/// Plays back a sequence of `(state, duration in frames)` steps, e.g. a ↓↘→+A command input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MacroPlayer<'a> {
+    steps: &'a [(State, u32)],
+    step: usize,
+    elapsed: u32,
+    playing: bool,
+}
+
+impl<'a> MacroPlayer<'a> {
+    pub fn new(steps: &'a [(State, u32)]) -> Self {
+        Self {
+            steps,
+            step: 0,
+            elapsed: 0,
+            playing: false,
+        }
+    }
+
+    /// Starts the macro from its first step, restarting it if it is already playing.
+    pub fn trigger(&mut self) {
+        self.step = 0;
+        self.elapsed = 0;
+        self.playing = true;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Advances the macro by one frame.
+    /// While playing, the buttons of `state` are replaced with those of the current step; otherwise it is left untouched.
+    pub fn update(&mut self, state: &mut State) {
+        if !self.playing {
+            return;
+        }
+
+        while let Some(&(step, frames)) = self.steps.get(self.step) {
+            if self.elapsed < frames {
+                state.apply_mask(step.pressed_mask());
+                self.elapsed += 1;
+                return;
+            }
+
+            self.step += 1;
+            self.elapsed = 0;
+        }
+
+        self.playing = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BUTTON_A, BUTTON_DOWN, BUTTON_RIGHT, BUTTON_Y};
+
+    fn pressed(mask: u16) -> State {
+        let mut state = State::default();
+        state.apply_mask(mask);
+        state
+    }
+
+    #[test]
+    fn plays_three_steps_for_their_durations() {
+        let steps = [
+            (pressed(BUTTON_DOWN), 2),
+            (pressed(BUTTON_DOWN | BUTTON_RIGHT), 1),
+            (pressed(BUTTON_RIGHT | BUTTON_A), 2),
+        ];
+        let mut player = MacroPlayer::new(&steps);
+        player.trigger();
+
+        let expected = [
+            BUTTON_DOWN,
+            BUTTON_DOWN,
+            BUTTON_DOWN | BUTTON_RIGHT,
+            BUTTON_RIGHT | BUTTON_A,
+            BUTTON_RIGHT | BUTTON_A,
+        ];
+        for mask in expected {
+            let mut state = pressed(BUTTON_Y);
+            player.update(&mut state);
+            assert_eq!(state.pressed_mask(), mask);
+        }
+
+        let mut state = pressed(BUTTON_Y);
+        player.update(&mut state);
+        assert!(!player.is_playing());
+        assert_eq!(state.pressed_mask(), BUTTON_Y);
+    }
+
+    #[test]
+    fn idle_player_leaves_state_untouched() {
+        let steps = [(pressed(BUTTON_A), 1)];
+        let mut player = MacroPlayer::new(&steps);
+        let mut state = pressed(BUTTON_Y);
+
+        player.update(&mut state);
+
+        assert_eq!(state.pressed_mask(), BUTTON_Y);
+    }
+}
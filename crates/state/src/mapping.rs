@@ -0,0 +1,102 @@
+use crate::{offset, Button, WireError};
+
+/// Number of external inputs a mapping can describe.
+pub const MAPPING_LEN: usize = 16;
+
+const UNMAPPED: u8 = 0xFF;
+
+/// Describes which `Button` each external input index drives, if any.
+///
+/// Encoded as one byte per input: the button's bit offset, or `0xFF` for an unmapped input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Mapping {
+    inputs: [Option<Button>; MAPPING_LEN],
+}
+
+impl Default for Mapping {
+    /// Maps the first twelve inputs to the buttons in clock-out order and leaves the rest unmapped.
+    fn default() -> Self {
+        let mut inputs = [None; MAPPING_LEN];
+        for (input, button) in inputs.iter_mut().zip(Button::ALL) {
+            *input = Some(button);
+        }
+
+        Self { inputs }
+    }
+}
+
+impl Mapping {
+    pub const ENCODED_LEN: usize = MAPPING_LEN;
+
+    pub fn get(&self, input: usize) -> Option<Button> {
+        self.inputs.get(input).copied().flatten()
+    }
+
+    /// Sets the button driven by `input`. Indices past `MAPPING_LEN` are ignored.
+    pub fn set(&mut self, input: usize, button: Option<Button>) {
+        if let Some(slot) = self.inputs.get_mut(input) {
+            *slot = button;
+        }
+    }
+
+    /// Writes the mapping into the start of `buf` and returns the number of bytes written.
+    pub fn encode(&self, buf: &mut [u8]) -> Result<usize, WireError> {
+        let buf = buf
+            .get_mut(..Self::ENCODED_LEN)
+            .ok_or(WireError::BufferTooSmall)?;
+
+        for (byte, input) in buf.iter_mut().zip(self.inputs) {
+            *byte = input.map_or(UNMAPPED, |button| offset(button) as u8);
+        }
+
+        Ok(Self::ENCODED_LEN)
+    }
+
+    pub fn decode(buf: &[u8]) -> Result<Self, WireError> {
+        let buf = buf
+            .get(..Self::ENCODED_LEN)
+            .ok_or(WireError::BufferTooSmall)?;
+
+        let mut inputs = [None; MAPPING_LEN];
+        for (input, &byte) in inputs.iter_mut().zip(buf) {
+            *input = match byte {
+                UNMAPPED => None,
+                _ => Some(
+                    *Button::ALL
+                        .get(byte as usize)
+                        .ok_or(WireError::InvalidButton(byte))?,
+                ),
+            };
+        }
+
+        Ok(Self { inputs })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_identity_round_trip() {
+        let mut mapping = Mapping::default();
+        mapping.set(0, Some(Button::A));
+        mapping.set(8, Some(Button::B));
+        mapping.set(3, None);
+        mapping.set(14, Some(Button::Start));
+        let mut buf = [0; Mapping::ENCODED_LEN];
+
+        assert_eq!(mapping.encode(&mut buf), Ok(Mapping::ENCODED_LEN));
+        assert_eq!(buf[3], UNMAPPED);
+        assert_eq!(buf[14], offset(Button::Start) as u8);
+        assert_eq!(Mapping::decode(&buf), Ok(mapping));
+    }
+
+    #[test]
+    fn bytes_past_the_last_button_are_rejected() {
+        let mut buf = [UNMAPPED; Mapping::ENCODED_LEN];
+        buf[5] = 12;
+
+        assert_eq!(Mapping::decode(&buf), Err(WireError::InvalidButton(12)));
+    }
+}
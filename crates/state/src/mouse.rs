@@ -0,0 +1,134 @@
+/*
+Clock  Length Name            Values
+1      8      Unused          0
+9      1      Right button    0-1
+10     1      Left button     0-1
+11     2      Sensitivity     0-2
+13     4      Signature       0001
+17     1      Y direction     0 = Down, 1 = Up
+18     7      Y motion        0-127 (MSB first)
+25     1      X direction     0 = Right, 1 = Left
+26     7      X motion        0-127 (MSB first)
+
+1 = low on the data line, 0 = high (same polarity as the pad, where pressed is low)
+*/
+
+use crate::Peripheral;
+
+const MOTION_MAX: u8 = 0x7F;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Sensitivity {
+    #[default]
+    Low,
+    Medium,
+    High,
+}
+
+/// State of a SNES mouse, which is clocked out as 32 bits instead of the pad's 16.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SnesMouse {
+    /// Horizontal movement since the last latch, positive to the right.
+    pub x: i8,
+    /// Vertical movement since the last latch, positive downwards.
+    pub y: i8,
+    pub left: bool,
+    pub right: bool,
+    pub sensitivity: Sensitivity,
+}
+
+impl SnesMouse {
+    /// The 32 bits in clock-out order, the first clocked bit being the most significant.
+    /// Movement is clamped to the 7-bit magnitude the protocol can carry.
+    pub fn bits(&self) -> u32 {
+        let mut bits = 0;
+
+        bits |= (self.right as u32) << 23;
+        bits |= (self.left as u32) << 22;
+        bits |= (self.sensitivity as u32) << 20;
+        bits |= (Peripheral::Mouse.signature() as u32) << 16;
+        bits |= ((self.y < 0) as u32) << 15;
+        bits |= (self.y.unsigned_abs().min(MOTION_MAX) as u32) << 8;
+        bits |= ((self.x < 0) as u32) << 7;
+        bits |= self.x.unsigned_abs().min(MOTION_MAX) as u32;
+
+        bits
+    }
+
+    /// Data line levels for the 32 clocks (`true` = high), ready to be shifted out.
+    pub fn bit_sequence(&self) -> [bool; 32] {
+        let bits = self.bits();
+        let mut sequence = [true; 32];
+
+        for (clock, level) in sequence.iter_mut().enumerate() {
+            *level = bits >> (31 - clock) & 1 == 0;
+        }
+
+        sequence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNATURE: u32 = 0b0001 << 16;
+
+    #[test]
+    fn idle_mouse_only_sends_the_signature() {
+        assert_eq!(SnesMouse::default().bits(), SIGNATURE);
+    }
+
+    #[test]
+    fn positive_movement_is_down_and_right() {
+        let mouse = SnesMouse {
+            x: 5,
+            y: 3,
+            ..SnesMouse::default()
+        };
+
+        assert_eq!(mouse.bits(), SIGNATURE | 3 << 8 | 5);
+    }
+
+    #[test]
+    fn negative_movement_sets_the_direction_bits() {
+        let mouse = SnesMouse {
+            x: -5,
+            y: -3,
+            ..SnesMouse::default()
+        };
+
+        assert_eq!(mouse.bits(), SIGNATURE | 1 << 15 | 3 << 8 | 1 << 7 | 5);
+    }
+
+    #[test]
+    fn buttons_and_sensitivity_bits() {
+        let mouse = SnesMouse {
+            left: true,
+            right: true,
+            sensitivity: Sensitivity::High,
+            ..SnesMouse::default()
+        };
+
+        assert_eq!(mouse.bits(), 1 << 23 | 1 << 22 | 2 << 20 | SIGNATURE);
+
+        let sequence = mouse.bit_sequence();
+        assert!(!sequence[8], "right button low on clock 9");
+        assert!(!sequence[9], "left button low on clock 10");
+        assert!(sequence[..8].iter().all(|&level| level));
+    }
+
+    #[test]
+    fn motion_is_clamped_to_seven_bits() {
+        let mouse = SnesMouse {
+            x: i8::MIN,
+            y: i8::MAX,
+            ..SnesMouse::default()
+        };
+
+        assert_eq!(
+            mouse.bits(),
+            SIGNATURE | (MOTION_MAX as u32) << 8 | 1 << 7 | MOTION_MAX as u32
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use core::ops::{BitAnd, BitOr, BitXor};
+
+use crate::State;
+
+// The operators combine pressed buttons rather than raw wire bits, so `a | b` holds every button
+// pressed in either state. The cycle is always taken from the left operand.
+
+impl BitOr for State {
+    type Output = State;
+
+    fn bitor(mut self, rhs: State) -> State {
+        self.apply_mask(self.pressed_mask() | rhs.pressed_mask());
+        self
+    }
+}
+
+impl BitAnd for State {
+    type Output = State;
+
+    fn bitand(mut self, rhs: State) -> State {
+        self.apply_mask(self.pressed_mask() & rhs.pressed_mask());
+        self
+    }
+}
+
+impl BitXor for State {
+    type Output = State;
+
+    fn bitxor(mut self, rhs: State) -> State {
+        self.apply_mask(self.pressed_mask() ^ rhs.pressed_mask());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{State, BUTTON_A, BUTTON_B, BUTTON_X};
+
+    fn pressed(mask: u16) -> State {
+        let mut state = State::default();
+        state.apply_mask(mask);
+        state
+    }
+
+    #[test]
+    fn bitor_is_the_union_of_pressed_buttons() {
+        let a = pressed(BUTTON_A | BUTTON_X);
+        let b = pressed(BUTTON_B);
+
+        assert_eq!((a | b).pressed_mask(), BUTTON_A | BUTTON_B | BUTTON_X);
+        assert_eq!((a & b).pressed_mask(), 0);
+        assert_eq!((a ^ pressed(BUTTON_A)).pressed_mask(), BUTTON_X);
+    }
+
+    #[test]
+    fn cycle_comes_from_the_left_operand() {
+        let mut left = pressed(BUTTON_A);
+        left.next();
+        left.next();
+        let mut right = pressed(BUTTON_B);
+        right.next();
+
+        assert_eq!((left | right).cycle(), 2);
+        assert_eq!((right | left).cycle(), 1);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::WireError;
+
+/// Output channels relayed from the host to an enhanced controller, the counterpart of `State`.
+///
+/// Encoded as two bytes: rumble intensity, then the LED bitmask.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OutputState {
+    /// Rumble intensity, 0 is off and 255 is strongest.
+    pub rumble: u8,
+    /// One bit per player LED, bit 0 being the first.
+    pub leds: u8,
+}
+
+impl OutputState {
+    pub const ENCODED_LEN: usize = 2;
+
+    /// Writes the state into the start of `buf` and returns the number of bytes written.
+    pub fn encode(&self, buf: &mut [u8]) -> Result<usize, WireError> {
+        let buf = buf
+            .get_mut(..Self::ENCODED_LEN)
+            .ok_or(WireError::BufferTooSmall)?;
+
+        buf.copy_from_slice(&[self.rumble, self.leds]);
+
+        Ok(Self::ENCODED_LEN)
+    }
+
+    /// Reads the state from e.g. a GATT write. Trailing bytes are ignored.
+    pub fn decode(buf: &[u8]) -> Result<Self, WireError> {
+        let &[rumble, leds, ..] = buf else {
+            return Err(WireError::BufferTooSmall);
+        };
+
+        Ok(Self { rumble, leds })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rumble_and_leds_round_trip() {
+        let output = OutputState {
+            rumble: 200,
+            leds: 0b0101,
+        };
+        let mut buf = [0; 3];
+
+        assert_eq!(output.encode(&mut buf), Ok(OutputState::ENCODED_LEN));
+        assert_eq!(buf[..2], [200, 0b0101]);
+        assert_eq!(OutputState::decode(&buf), Ok(output));
+    }
+
+    #[test]
+    fn short_buffers_are_rejected() {
+        assert_eq!(
+            OutputState::default().encode(&mut [0]),
+            Err(WireError::BufferTooSmall)
+        );
+        assert_eq!(OutputState::decode(&[255]), Err(WireError::BufferTooSmall));
+    }
+}
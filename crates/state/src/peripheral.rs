@@ -0,0 +1,20 @@
+/// The kind of device the adapter presents itself as, which decides the ID bits after the buttons.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Peripheral {
+    /// Standard pad, returns 0000 (all high).
+    #[default]
+    Controller,
+    /// SNES mouse, returns 0001.
+    Mouse,
+}
+
+impl Peripheral {
+    /// The four ID bits returned on clocks 13 to 16, first clocked bit most significant.
+    /// A 1 is read by the console when the line is low.
+    pub const fn signature(self) -> u8 {
+        match self {
+            Peripheral::Controller => 0b0000,
+            Peripheral::Mouse => 0b0001,
+        }
+    }
+}
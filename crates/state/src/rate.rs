@@ -0,0 +1,90 @@
+use crate::{Frame, FrameSource, State};
+
+/// Caps how often state notifications go out.
+///
+/// A state is only sent if its buttons differ from the last sent state and at least `min_interval` frames have
+/// passed since then. Changes arriving sooner are coalesced: only the latest state is sent once the interval is over.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimiter {
+    min_interval: u32,
+    last_sent: Option<(Frame, State)>,
+}
+
+impl RateLimiter {
+    pub fn new(min_interval: u32) -> Self {
+        Self {
+            min_interval,
+            last_sent: None,
+        }
+    }
+
+    /// Feeds the state of the current frame of `frames` and returns true if it should be sent now.
+    pub fn update(&mut self, frames: &impl FrameSource, state: State) -> bool {
+        let frame = frames.frame();
+
+        match self.last_sent {
+            Some((at, sent))
+                if sent.diff(&state).is_empty() || frame.since(at) < self.min_interval =>
+            {
+                false
+            }
+            _ => {
+                self.last_sent = Some((frame, state));
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ManualFrames, BUTTON_A, BUTTON_B};
+
+    fn pressed(mask: u16) -> State {
+        let mut state = State::default();
+        state.apply_mask(mask);
+        state
+    }
+
+    #[test]
+    fn interval_is_measured_on_the_frame_source() {
+        let mut frames = ManualFrames::default();
+        let mut limiter = RateLimiter::new(3);
+
+        assert!(limiter.update(&frames, pressed(BUTTON_A)));
+
+        frames.advance_by(2);
+        assert!(!limiter.update(&frames, pressed(BUTTON_B)));
+
+        frames.advance();
+        assert!(limiter.update(&frames, pressed(BUTTON_B)));
+    }
+
+    #[test]
+    fn rapid_changes_coalesce_into_one_send() {
+        let mut frames = ManualFrames::default();
+        let mut limiter = RateLimiter::new(4);
+
+        assert!(limiter.update(&frames, pressed(BUTTON_A)));
+
+        let mut sent = 0;
+        for mask in [BUTTON_B, BUTTON_A | BUTTON_B, BUTTON_B, 0] {
+            frames.advance();
+            sent += limiter.update(&frames, pressed(mask)) as u32;
+        }
+
+        assert_eq!(sent, 1, "only the change on the fourth frame goes out");
+    }
+
+    #[test]
+    fn unchanged_state_is_not_resent() {
+        let mut frames = ManualFrames::default();
+        let mut limiter = RateLimiter::new(1);
+        let state = pressed(BUTTON_A);
+
+        assert!(limiter.update(&frames, state));
+        frames.advance_by(10);
+        assert!(!limiter.update(&frames, state));
+    }
+}
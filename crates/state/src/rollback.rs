@@ -0,0 +1,122 @@
+use crate::{Frame, State};
+
+/// Input history of the last `N` saved frames, the primitive rollback netcode rewinds to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RollbackBuffer<const N: usize> {
+    entries: [(Frame, State); N],
+    /// Index the next entry is written to.
+    head: usize,
+    len: usize,
+}
+
+impl<const N: usize> Default for RollbackBuffer<N> {
+    fn default() -> Self {
+        Self {
+            entries: [(Frame::default(), State::default()); N],
+            head: 0,
+            len: 0,
+        }
+    }
+}
+
+impl<const N: usize> RollbackBuffer<N> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Stores the state of `frame`, overwriting the oldest frame once the buffer is full.
+    pub fn save(&mut self, frame: Frame, state: State) {
+        if N == 0 {
+            return;
+        }
+
+        self.entries[self.head] = (frame, state);
+        self.head = (self.head + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+
+    pub fn load(&self, frame: Frame) -> Option<State> {
+        self.saved()
+            .find(|(saved, _)| *saved == frame)
+            .map(|(_, state)| state)
+    }
+
+    /// Discards every frame saved after `frame` and returns its state.
+    /// Nothing is discarded if `frame` is not in the buffer.
+    pub fn rollback_to(&mut self, frame: Frame) -> Option<State> {
+        let newer = self.saved().position(|(saved, _)| saved == frame)?;
+
+        self.head = (self.head + N - newer) % N;
+        self.len -= newer;
+
+        self.load(frame)
+    }
+
+    /// Saved entries from newest to oldest.
+    fn saved(&self) -> impl Iterator<Item = (Frame, State)> + '_ {
+        (1..=self.len).map(move |back| self.entries[(self.head + N - back) % N])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pressed(mask: u16) -> State {
+        let mut state = State::default();
+        state.apply_mask(mask);
+        state
+    }
+
+    #[test]
+    fn save_load_rollback_and_reapply() {
+        let mut buffer = RollbackBuffer::<8>::default();
+        for n in 0..5 {
+            buffer.save(Frame(n), pressed(1 << n));
+        }
+
+        assert_eq!(buffer.load(Frame(2)), Some(pressed(1 << 2)));
+        assert_eq!(buffer.rollback_to(Frame(2)), Some(pressed(1 << 2)));
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.load(Frame(3)), None);
+
+        buffer.save(Frame(3), pressed(1 << 8));
+        buffer.save(Frame(4), pressed(1 << 9));
+
+        assert_eq!(buffer.load(Frame(3)), Some(pressed(1 << 8)));
+        assert_eq!(buffer.load(Frame(4)), Some(pressed(1 << 9)));
+        assert_eq!(buffer.len(), 5);
+    }
+
+    #[test]
+    fn oldest_frames_are_overwritten_once_full() {
+        let mut buffer = RollbackBuffer::<3>::default();
+        for n in 0..5 {
+            buffer.save(Frame(n), pressed(1 << n));
+        }
+
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.load(Frame(1)), None);
+        assert_eq!(buffer.load(Frame(2)), Some(pressed(1 << 2)));
+        assert_eq!(buffer.load(Frame(4)), Some(pressed(1 << 4)));
+
+        assert_eq!(buffer.rollback_to(Frame(3)), Some(pressed(1 << 3)));
+        buffer.save(Frame(4), pressed(0));
+        assert_eq!(buffer.load(Frame(2)), Some(pressed(1 << 2)));
+        assert_eq!(buffer.load(Frame(4)), Some(pressed(0)));
+    }
+
+    #[test]
+    fn unknown_frames_roll_nothing_back() {
+        let mut buffer = RollbackBuffer::<4>::default();
+        buffer.save(Frame(7), pressed(0));
+
+        assert_eq!(buffer.rollback_to(Frame(1)), None);
+        assert_eq!(buffer.len(), 1);
+        assert!(!buffer.is_empty());
+    }
+}
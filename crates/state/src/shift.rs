@@ -0,0 +1,251 @@
+use embedded_hal::digital::PinState;
+
+use crate::{Peripheral, State, CYCLES};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BitOrder {
+    /// B first, as on the SNES.
+    #[default]
+    LsbFirst,
+    /// The last clocked bit first.
+    MsbFirst,
+}
+
+/// How the state word is put on the data line, so clones with a different protocol can be served too.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WireConfig {
+    pub bit_order: BitOrder,
+    /// Pressed buttons drive the line low when set.
+    pub active_low: bool,
+    /// Number of clock pulses per latch.
+    pub cycles: u8,
+    /// Device whose ID signature is sent on clocks 13 to 16. Clocks after those read as 1, like a pad whose
+    /// shift register has run empty.
+    pub peripheral: Peripheral,
+}
+
+impl WireConfig {
+    pub const SNES: WireConfig = WireConfig {
+        bit_order: BitOrder::LsbFirst,
+        active_low: true,
+        cycles: 16,
+        peripheral: Peripheral::Controller,
+    };
+}
+
+impl Default for WireConfig {
+    fn default() -> Self {
+        Self::SNES
+    }
+}
+
+impl State {
+    /// Like `next_as`, but while `presence` is `Disconnected` the line idles at the level of an empty port.
+    /// The cycle still advances, so reconnecting takes effect on the next clock.
+    pub fn next_with(&mut self, peripheral: Peripheral, presence: Presence) -> bool {
+        presence.drive(self.next_as(peripheral))
+    }
+
+    /// Like `data_level`, but reports the level of an empty port while `presence` is `Disconnected`.
+    pub fn data_level_with(&self, presence: Presence) -> PinState {
+        presence.drive(self.data_level().into()).into()
+    }
+
+    /// Data line levels (`true` = high) for each clock of one latch under `config`.
+    pub fn bit_sequence(&self, config: WireConfig) -> impl Iterator<Item = bool> {
+        let state = *self;
+        let cycles = config.cycles as u16;
+
+        (0..cycles).map(move |clock| {
+            let bit = match config.bit_order {
+                BitOrder::LsbFirst => clock,
+                BitOrder::MsbFirst => cycles - 1 - clock,
+            };
+            let asserted = match bit {
+                x if x < 12 => !state.read_bit(x),
+                x if x < CYCLES => config.peripheral.signature() >> (CYCLES - 1 - x) & 1 == 1,
+                _ => true,
+            };
+
+            asserted != config.active_low
+        })
+    }
+}
+
+/// Whether the console should see a controller on the port, so the adapter can simulate unplugging it
+/// (e.g. while the BLE link is down) instead of clocking out a neutral pad.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Presence {
+    #[default]
+    Connected,
+    Disconnected,
+}
+
+impl Presence {
+    /// Level an empty port idles at: the console's pull-up keeps the line high, which reads as all zeros.
+    /// A connected pad instead pulls the line low once its 16 bits are out, which is how the console tells them apart.
+    pub const DISCONNECTED_LEVEL: bool = true;
+
+    /// The level to drive for a clock whose state bit is `level`.
+    pub fn drive(self, level: bool) -> bool {
+        match self {
+            Presence::Connected => level,
+            Presence::Disconnected => Self::DISCONNECTED_LEVEL,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BUTTON_B, BUTTON_R};
+
+    fn pressed(mask: u16) -> State {
+        let mut state = State::default();
+        state.apply_mask(mask);
+        state
+    }
+
+    /// Packs the levels so that bit `n` is the level on clock `n + 1`.
+    fn levels(state: &State, config: WireConfig) -> u16 {
+        state
+            .bit_sequence(config)
+            .enumerate()
+            .fold(0, |levels, (clock, high)| levels | (high as u16) << clock)
+    }
+
+    #[test]
+    fn every_bit_order_and_polarity() {
+        let state = pressed(BUTTON_B | BUTTON_R);
+        let cases = [
+            (BitOrder::LsbFirst, true, 0b1111_0111_1111_1110),
+            (BitOrder::LsbFirst, false, 0b0000_1000_0000_0001),
+            (BitOrder::MsbFirst, true, 0b0111_1111_1110_1111),
+            (BitOrder::MsbFirst, false, 0b1000_0000_0001_0000),
+        ];
+
+        for (bit_order, active_low, expected) in cases {
+            let config = WireConfig {
+                bit_order,
+                active_low,
+                ..WireConfig::SNES
+            };
+
+            assert_eq!(levels(&state, config), expected, "{config:?}");
+        }
+    }
+
+    #[test]
+    fn cycles_sets_the_sequence_length() {
+        let config = WireConfig {
+            cycles: 8,
+            ..WireConfig::SNES
+        };
+
+        assert_eq!(State::default().bit_sequence(config).count(), 8);
+        assert_eq!(State::default().bit_sequence(WireConfig::SNES).count(), 16);
+    }
+
+    #[test]
+    fn standard_pad_signature_is_all_high() {
+        let config = WireConfig {
+            cycles: 20,
+            ..WireConfig::SNES
+        };
+
+        for (clock, high) in State::default().bit_sequence(config).enumerate() {
+            assert_eq!(high, clock < 16, "clock {}", clock + 1);
+        }
+    }
+
+    #[test]
+    fn mouse_signature_pulls_clock_16_low() {
+        let config = WireConfig {
+            peripheral: Peripheral::Mouse,
+            ..WireConfig::SNES
+        };
+        let levels = levels(&State::default(), config);
+
+        assert_eq!(levels >> 12, 0b0111);
+    }
+
+    #[test]
+    fn matches_next_as_for_each_peripheral() {
+        let state = pressed(BUTTON_B | BUTTON_R);
+
+        for peripheral in [Peripheral::Controller, Peripheral::Mouse] {
+            let config = WireConfig {
+                cycles: 18,
+                peripheral,
+                ..WireConfig::SNES
+            };
+            let mut clocked = state;
+
+            for (clock, level) in state.bit_sequence(config).enumerate() {
+                assert_eq!(
+                    level,
+                    clocked.next_as(peripheral),
+                    "{peripheral:?} clock {}",
+                    clock + 1
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn disconnected_idles_high_whatever_the_state() {
+        let states = [
+            State::default(),
+            State::all_buttons_pressed(),
+            pressed(BUTTON_B | BUTTON_R),
+        ];
+
+        for state in states {
+            for peripheral in [Peripheral::Controller, Peripheral::Mouse] {
+                let mut state = state;
+                for clock in 0..20 {
+                    assert_eq!(
+                        state.data_level_with(Presence::Disconnected),
+                        PinState::High
+                    );
+                    assert_eq!(
+                        state.next_with(peripheral, Presence::Disconnected),
+                        Presence::DISCONNECTED_LEVEL,
+                        "{state:?} clock {}",
+                        clock + 1
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn connected_neutral_pad_differs_from_an_empty_port() {
+        let mut state = State::default();
+
+        for clock in 0..CYCLES {
+            assert_eq!(state.data_level_with(Presence::Connected), PinState::High);
+            assert!(
+                state.next_with(Peripheral::Controller, Presence::Connected),
+                "clock {}",
+                clock + 1
+            );
+        }
+
+        assert_eq!(state.data_level_with(Presence::Connected), PinState::Low);
+        assert!(!state.next_with(Peripheral::Controller, Presence::Connected));
+    }
+
+    #[test]
+    fn connected_matches_next_as() {
+        let state = pressed(BUTTON_B | BUTTON_R);
+        let (mut plain, mut driven) = (state, state);
+
+        for _ in 0..18 {
+            assert_eq!(
+                driven.next_with(Peripheral::Mouse, Presence::Connected),
+                plain.next_as(Peripheral::Mouse)
+            );
+        }
+    }
+}
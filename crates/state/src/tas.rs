@@ -0,0 +1,158 @@
+use core::fmt::Write;
+
+use crate::{Button, State, WireError};
+
+/// Button letters of the text format in column order, paired with the button they stand for.
+const COLUMNS: [(char, Button); 12] = [
+    ('U', Button::Up),
+    ('D', Button::Down),
+    ('L', Button::Left),
+    ('R', Button::Right),
+    ('s', Button::Select),
+    ('S', Button::Start),
+    ('Y', Button::Y),
+    ('B', Button::B),
+    ('A', Button::A),
+    ('X', Button::X),
+    ('l', Button::L),
+    ('r', Button::R),
+];
+
+/// Frame-by-frame input log of up to `N` frames for tool-assisted speedrun tools.
+///
+/// The binary format is one little-endian state word per frame.
+/// The text format is one `|UDLRsSYBAXlr|` line per frame with `.` for released buttons.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TasLog<const N: usize> {
+    frames: [State; N],
+    len: usize,
+}
+
+impl<const N: usize> Default for TasLog<N> {
+    fn default() -> Self {
+        Self {
+            frames: [State::default(); N],
+            len: 0,
+        }
+    }
+}
+
+impl<const N: usize> TasLog<N> {
+    /// Appends the state of the next frame, returning false if the log is full.
+    pub fn record(&mut self, state: State) -> bool {
+        let Some(frame) = self.frames.get_mut(self.len) else {
+            return false;
+        };
+
+        *frame = state;
+        self.len += 1;
+
+        true
+    }
+
+    pub fn frames(&self) -> &[State] {
+        &self.frames[..self.len]
+    }
+
+    /// Writes the binary log into the start of `buf` and returns the number of bytes written.
+    pub fn encode(&self, buf: &mut [u8]) -> Result<usize, WireError> {
+        let len = self.len * 2;
+        let buf = buf.get_mut(..len).ok_or(WireError::BufferTooSmall)?;
+
+        for (bytes, state) in buf.as_chunks_mut::<2>().0.iter_mut().zip(self.frames()) {
+            *bytes = state.value.to_le_bytes();
+        }
+
+        Ok(len)
+    }
+
+    /// Reads a binary log. Fails if `buf` ends in the middle of a frame or holds more than `N` frames.
+    pub fn decode(buf: &[u8]) -> Result<Self, WireError> {
+        let (words, rest) = buf.as_chunks::<2>();
+        if !rest.is_empty() {
+            return Err(WireError::Truncated);
+        }
+
+        let mut log = Self::default();
+        for &bytes in words {
+            let state = State::from_u16(u16::from_le_bytes(bytes));
+
+            if !log.record(state) {
+                return Err(WireError::CapacityExceeded);
+            }
+        }
+
+        Ok(log)
+    }
+
+    pub fn write_text(&self, w: &mut impl Write) -> core::fmt::Result {
+        for state in self.frames() {
+            w.write_char('|')?;
+            for (letter, button) in COLUMNS {
+                w.write_char(if state.is_pressed(button) {
+                    letter
+                } else {
+                    '.'
+                })?;
+            }
+            w.write_str("|\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+
+    use super::*;
+    use crate::{BUTTON_A, BUTTON_B, BUTTON_RIGHT, BUTTON_START};
+
+    fn short_log() -> TasLog<4> {
+        let mut log = TasLog::default();
+        for mask in [0, BUTTON_RIGHT, BUTTON_RIGHT | BUTTON_B, BUTTON_START] {
+            assert!(log.record(State::with_buttons_pressed(mask)));
+        }
+        log
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let log = short_log();
+        let mut buf = [0; 8];
+
+        assert_eq!(log.encode(&mut buf), Ok(8));
+        assert_eq!(TasLog::<4>::decode(&buf), Ok(log));
+    }
+
+    #[test]
+    fn odd_length_input_is_truncated() {
+        let mut buf = [0; 8];
+        short_log().encode(&mut buf).unwrap();
+
+        assert_eq!(TasLog::<4>::decode(&buf[..7]), Err(WireError::Truncated));
+    }
+
+    #[test]
+    fn longer_logs_exceed_capacity() {
+        let mut buf = [0; 8];
+        short_log().encode(&mut buf).unwrap();
+
+        assert_eq!(TasLog::<3>::decode(&buf), Err(WireError::CapacityExceeded));
+        assert!(!short_log().record(State::with_buttons_pressed(BUTTON_A)));
+    }
+
+    #[test]
+    fn text_lines_use_button_letters() {
+        let mut text = String::new();
+        short_log().write_text(&mut text).unwrap();
+
+        assert_eq!(
+            text,
+            "|............|\n|...R........|\n|...R...B....|\n|.....S......|\n"
+        );
+    }
+}
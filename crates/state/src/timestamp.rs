@@ -0,0 +1,53 @@
+use crate::State;
+
+/// A state together with the time it was sampled and, once known, the time it was clocked out to the console.
+/// Timestamps are in whatever monotonic unit the caller's timer provides.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimestampedState {
+    pub state: State,
+    pub captured_at: u64,
+    pub clocked_out_at: Option<u64>,
+}
+
+impl TimestampedState {
+    pub fn new(state: State, captured_at: u64) -> Self {
+        Self {
+            state,
+            captured_at,
+            clocked_out_at: None,
+        }
+    }
+
+    pub fn mark_clocked_out(&mut self, at: u64) {
+        self.clocked_out_at = Some(at);
+    }
+
+    /// Time between sampling and shift-out, or `None` if the state was not clocked out yet.
+    /// A clock-out timestamp earlier than the capture yields 0.
+    pub fn latency(&self) -> Option<u64> {
+        self.clocked_out_at
+            .map(|at| at.saturating_sub(self.captured_at))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_is_clock_out_minus_capture() {
+        let mut stamped = TimestampedState::new(State::default(), 1_000);
+        assert_eq!(stamped.latency(), None);
+
+        stamped.mark_clocked_out(1_250);
+        assert_eq!(stamped.latency(), Some(250));
+    }
+
+    #[test]
+    fn clock_out_before_capture_is_zero_latency() {
+        let mut stamped = TimestampedState::new(State::default(), 1_000);
+        stamped.mark_clocked_out(900);
+
+        assert_eq!(stamped.latency(), Some(0));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{Frame, FrameSource, State, BUTTON_L, BUTTON_R, BUTTON_SELECT, BUTTON_START};
+
+/// L + R + Select + Start, the soft reset combo most games use.
+pub const SOFT_RESET_COMBO: u16 = BUTTON_L | BUTTON_R | BUTTON_SELECT | BUTTON_START;
+
+impl State {
+    pub fn is_soft_reset(&self) -> bool {
+        self.all_pressed(SOFT_RESET_COMBO)
+    }
+}
+
+/// Fires a soft reset only after the combo was held for a number of consecutive frames,
+/// so brushing over the buttons does not reset the game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SoftResetWatchdog {
+    threshold: u32,
+    held_since: Option<Frame>,
+    fired: bool,
+}
+
+impl SoftResetWatchdog {
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            threshold,
+            held_since: None,
+            fired: false,
+        }
+    }
+
+    /// Feeds the state of the current frame of `frames` and calls `on_reset` once the combo has been held
+    /// for `threshold` frames, counting the first one. It fires once per hold; the combo has to be released
+    /// before it can fire again.
+    pub fn update(&mut self, frames: &impl FrameSource, state: &State, on_reset: impl FnOnce()) {
+        if !state.is_soft_reset() {
+            self.held_since = None;
+            self.fired = false;
+            return;
+        }
+
+        let now = frames.frame();
+        let since = *self.held_since.get_or_insert(now);
+
+        if !self.fired && now.since(since).saturating_add(1) >= self.threshold.max(1) {
+            self.fired = true;
+            on_reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ManualFrames, BUTTON_A};
+
+    fn feed(
+        watchdog: &mut SoftResetWatchdog,
+        frames: &mut ManualFrames,
+        state: State,
+        count: u32,
+    ) -> u32 {
+        let mut resets = 0;
+        for _ in 0..count {
+            watchdog.update(frames, &state, || resets += 1);
+            frames.advance();
+        }
+        resets
+    }
+
+    #[test]
+    fn brief_combo_does_not_reset() {
+        let mut watchdog = SoftResetWatchdog::new(30);
+        let mut frames = ManualFrames::default();
+        let combo = State::with_buttons_pressed(SOFT_RESET_COMBO);
+
+        assert_eq!(feed(&mut watchdog, &mut frames, combo, 29), 0);
+        assert_eq!(feed(&mut watchdog, &mut frames, State::default(), 1), 0);
+        assert_eq!(feed(&mut watchdog, &mut frames, combo, 29), 0);
+    }
+
+    #[test]
+    fn sustained_combo_resets_once_per_hold() {
+        let mut watchdog = SoftResetWatchdog::new(30);
+        let mut frames = ManualFrames::default();
+        let combo = State::with_buttons_pressed(SOFT_RESET_COMBO | BUTTON_A);
+
+        assert_eq!(feed(&mut watchdog, &mut frames, combo, 29), 0);
+        assert_eq!(feed(&mut watchdog, &mut frames, combo, 1), 1);
+        assert_eq!(feed(&mut watchdog, &mut frames, combo, 100), 0);
+
+        feed(&mut watchdog, &mut frames, State::default(), 1);
+        assert_eq!(feed(&mut watchdog, &mut frames, combo, 30), 1);
+    }
+
+    #[test]
+    fn hold_time_is_measured_in_frames() {
+        let mut watchdog = SoftResetWatchdog::new(30);
+        let mut frames = ManualFrames::default();
+        let combo = State::with_buttons_pressed(SOFT_RESET_COMBO);
+        let mut resets = 0;
+
+        watchdog.update(&frames, &combo, || resets += 1);
+        frames.advance_by(29);
+        watchdog.update(&frames, &combo, || resets += 1);
+
+        assert_eq!(resets, 1);
+    }
+}
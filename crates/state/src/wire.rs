@@ -0,0 +1,123 @@
+use core::fmt::Display;
+
+use crate::State;
+
+/// Format version prefixed to every stored state. Bump it whenever the stored layout changes.
+pub const STATE_WIRE_VERSION: u8 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WireError {
+    BufferTooSmall,
+    VersionMismatch { expected: u8, found: u8 },
+    InvalidButton(u8),
+    CapacityExceeded,
+    Truncated,
+}
+
+impl Display for WireError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            WireError::BufferTooSmall => write!(f, "buffer too small"),
+            WireError::VersionMismatch { expected, found } => {
+                write!(
+                    f,
+                    "wire version mismatch: expected {expected}, found {found}"
+                )
+            }
+            WireError::InvalidButton(value) => write!(f, "invalid button offset {value}"),
+            WireError::CapacityExceeded => write!(f, "more entries than the destination can hold"),
+            WireError::Truncated => write!(f, "input ends in the middle of a record"),
+        }
+    }
+}
+
+impl State {
+    /// Length of the versioned encoding: one version byte followed by the little-endian state word.
+    pub const VERSIONED_LEN: usize = 3;
+
+    /// Writes the versioned encoding into the start of `buf` and returns the number of bytes written.
+    pub fn write_versioned(&self, buf: &mut [u8]) -> Result<usize, WireError> {
+        let buf = buf
+            .get_mut(..Self::VERSIONED_LEN)
+            .ok_or(WireError::BufferTooSmall)?;
+
+        buf[0] = STATE_WIRE_VERSION;
+        buf[1..].copy_from_slice(&self.value.to_le_bytes());
+
+        Ok(Self::VERSIONED_LEN)
+    }
+
+    /// Reads a state written by `write_versioned`, rejecting any other format version.
+    pub fn read_versioned(buf: &[u8]) -> Result<State, WireError> {
+        let buf = buf
+            .get(..Self::VERSIONED_LEN)
+            .ok_or(WireError::BufferTooSmall)?;
+
+        if buf[0] != STATE_WIRE_VERSION {
+            return Err(WireError::VersionMismatch {
+                expected: STATE_WIRE_VERSION,
+                found: buf[0],
+            });
+        }
+
+        Ok(State::from_u16(u16::from_le_bytes([buf[1], buf[2]])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Button;
+
+    #[test]
+    fn versioned_round_trip() {
+        let mut state = State::default();
+        state.press(Button::A, true);
+        state.press(Button::Start, true);
+        state.next();
+        let mut buf = [0; 4];
+
+        assert_eq!(state.write_versioned(&mut buf), Ok(State::VERSIONED_LEN));
+        assert_eq!(buf[0], STATE_WIRE_VERSION);
+        assert_eq!(State::read_versioned(&buf), Ok(state));
+    }
+
+    #[test]
+    fn shifted_out_state_round_trips() {
+        let mut state = State::default();
+        state.press(Button::B, true);
+        for _ in 0..16 {
+            state.next();
+        }
+        let mut buf = [0; State::VERSIONED_LEN];
+        state.write_versioned(&mut buf).unwrap();
+
+        assert_eq!(State::read_versioned(&buf), Ok(state));
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let mut buf = [0; State::VERSIONED_LEN];
+        State::default().write_versioned(&mut buf).unwrap();
+        buf[0] = STATE_WIRE_VERSION + 1;
+
+        assert_eq!(
+            State::read_versioned(&buf),
+            Err(WireError::VersionMismatch {
+                expected: STATE_WIRE_VERSION,
+                found: STATE_WIRE_VERSION + 1,
+            })
+        );
+    }
+
+    #[test]
+    fn short_buffers_are_rejected() {
+        let mut buf = [0; State::VERSIONED_LEN - 1];
+
+        assert_eq!(
+            State::default().write_versioned(&mut buf),
+            Err(WireError::BufferTooSmall)
+        );
+        assert_eq!(State::read_versioned(&buf), Err(WireError::BufferTooSmall));
+    }
+}
@@ -73,15 +73,11 @@ fn interrupt_handler() {
 
     critical_section::with(|cs| {
         if CLOCK.borrow_ref(cs).as_ref().unwrap().is_interrupt_set() {
-            if STATE.borrow_ref(cs).as_ref().unwrap().cycle() == 16 {
-                SERIAL.borrow_ref_mut(cs).as_mut().unwrap().set_high();
-            } else {
-                SERIAL
-                    .borrow_ref_mut(cs)
-                    .as_mut()
-                    .unwrap()
-                    .set_level(STATE.borrow_ref_mut(cs).as_mut().unwrap().next().into());
-            }
+            SERIAL
+                .borrow_ref_mut(cs)
+                .as_mut()
+                .unwrap()
+                .set_level(STATE.borrow_ref_mut(cs).as_mut().unwrap().next().into());
 
             CLOCK.borrow_ref_mut(cs).as_mut().unwrap().clear_interrupt()
         };